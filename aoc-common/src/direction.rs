@@ -0,0 +1,120 @@
+//The directions that can be moved in on a grid, either just the four along the axes or those and
+//the four diagonals too. North is up the page, towards lower y.
+
+use std::fmt::{self, Display};
+
+use crate::point::{Point2, Scalar};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction4 {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction4 {
+    ///Clockwise from north
+    pub const ALL: [Direction4; 4] = [Direction4::North, Direction4::East, Direction4::South, Direction4::West];
+
+    pub fn turn_left(&self) -> Direction4 {
+        Direction4::ALL[(*self as usize + 3) % 4]
+    }
+
+    pub fn turn_right(&self) -> Direction4 {
+        Direction4::ALL[(*self as usize + 1) % 4]
+    }
+
+    pub fn reverse(&self) -> Direction4 {
+        Direction4::ALL[(*self as usize + 2) % 4]
+    }
+
+    ///The change in position from a step in this direction
+    pub fn offset<T: Scalar>(&self) -> Point2<T> {
+        let (one, zero) = (T::ONE, T::ZERO);
+        match self {
+            Direction4::North => Point2::new(zero, -one),
+            Direction4::East => Point2::new(one, zero),
+            Direction4::South => Point2::new(zero, one),
+            Direction4::West => Point2::new(-one, zero),
+        }
+    }
+}
+
+///Drawn as an arrow, as the puzzles draw a path
+impl Display for Direction4 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let arrow = match self {
+            Direction4::North => "^",
+            Direction4::East => ">",
+            Direction4::South => "v",
+            Direction4::West => "<",
+        };
+        write!(f, "{}", arrow)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    ///Clockwise from north
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    ///An eighth of a turn anticlockwise
+    pub fn turn_left(&self) -> Direction8 {
+        Direction8::ALL[(*self as usize + 7) % 8]
+    }
+
+    ///An eighth of a turn clockwise
+    pub fn turn_right(&self) -> Direction8 {
+        Direction8::ALL[(*self as usize + 1) % 8]
+    }
+
+    pub fn reverse(&self) -> Direction8 {
+        Direction8::ALL[(*self as usize + 4) % 8]
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        *self as usize % 2 == 1
+    }
+
+    ///The change in position from a step in this direction
+    pub fn offset<T: Scalar>(&self) -> Point2<T> {
+        let (one, zero) = (T::ONE, T::ZERO);
+        match self {
+            Direction8::North => Point2::new(zero, -one),
+            Direction8::NorthEast => Point2::new(one, -one),
+            Direction8::East => Point2::new(one, zero),
+            Direction8::SouthEast => Point2::new(one, one),
+            Direction8::South => Point2::new(zero, one),
+            Direction8::SouthWest => Point2::new(-one, one),
+            Direction8::West => Point2::new(-one, zero),
+            Direction8::NorthWest => Point2::new(-one, -one),
+        }
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(direction: Direction4) -> Direction8 {
+        Direction8::ALL[direction as usize * 2]
+    }
+}
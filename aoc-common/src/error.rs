@@ -0,0 +1,83 @@
+use std::fmt::{self, Display};
+use std::io;
+
+///What a fallible line parser returns, anything that can be turned into an error can be returned
+/// with `?`, and a message with `Err("...".into())`
+pub type ParseResult<S> = Result<S, Box<dyn std::error::Error>>;
+
+///Errors from running a solution, these carry enough context to find the problem in the input
+#[derive(Debug)]
+pub enum Error {
+    ///The input couldn't be opened or read, line_number is the line being read when it failed
+    Io {
+        file_name: String,
+        line_number: Option<usize>,
+        source: io::Error,
+    },
+    ///A line of the input couldn't be parsed
+    Parse {
+        file_name: String,
+        line_number: usize,
+        line: String,
+        message: String,
+    },
+    ///The input as a whole isn't laid out as expected, e.g. it is missing a section
+    Format {
+        file_name: String,
+        message: String,
+    },
+    ///A request to the Advent of Code website failed
+    Website {
+        url: String,
+        message: String,
+    },
+}
+
+impl Error {
+    pub fn io(file_name: &str, line_number: Option<usize>, source: io::Error) -> Error {
+        Error::Io { file_name: String::from(file_name), line_number, source }
+    }
+
+    pub fn parse(file_name: &str, line_number: usize, line: &str, message: impl Display) -> Error {
+        Error::Parse {
+            file_name: String::from(file_name),
+            line_number,
+            line: String::from(line),
+            message: message.to_string(),
+        }
+    }
+
+    pub fn format(file_name: &str, message: impl Display) -> Error {
+        Error::Format { file_name: String::from(file_name), message: message.to_string() }
+    }
+
+    pub fn website(url: &str, message: impl Display) -> Error {
+        Error::Website { url: String::from(url), message: message.to_string() }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { file_name, line_number: Some(line_number), source } =>
+                write!(f, "{}:{}: {}", file_name, line_number, source),
+            Error::Io { file_name, line_number: None, source } =>
+                write!(f, "{}: {}", file_name, source),
+            Error::Parse { file_name, line_number, line, message } =>
+                write!(f, "{}:{}: {}: '{}'", file_name, line_number, message, line),
+            Error::Format { file_name, message } =>
+                write!(f, "{}: {}", file_name, message),
+            Error::Website { url, message } =>
+                write!(f, "{}: {}", url, message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Parse { .. } | Error::Format { .. } | Error::Website { .. } => None,
+        }
+    }
+}
@@ -0,0 +1,406 @@
+//Two dimensional grids of cells, which a lot of the puzzles are set on. A dense grid is a rectangle
+//with every cell present, a sparse one only holds the cells of interest on an unbounded plane.
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut, RangeInclusive};
+
+use crate::point::Point2;
+
+pub type Scale = i64;
+
+///A position on a grid, y increases going down the page as the rows of the input do
+pub type Pos = Point2<Scale>;
+
+///An inclusive rectangle of positions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub min_x: Scale,
+    pub min_y: Scale,
+    pub max_x: Scale,
+    pub max_y: Scale,
+}
+
+impl Bounds {
+    ///Holds no positions, the bounds of an empty grid
+    pub const EMPTY: Bounds = Bounds::new(0, 0, -1, -1);
+
+    pub const fn new(min_x: Scale, min_y: Scale, max_x: Scale, max_y: Scale) -> Bounds {
+        Bounds { min_x, min_y, max_x, max_y }
+    }
+
+    ///The smallest bounds holding all of the positions
+    pub fn of(positions: impl IntoIterator<Item = Pos>) -> Bounds {
+        positions.into_iter().fold(Bounds::EMPTY, Bounds::including)
+    }
+
+    ///Grown, if need be, to hold pos
+    pub fn including(self, pos: Pos) -> Bounds {
+        if self.is_empty() {
+            return Bounds::new(pos.x, pos.y, pos.x, pos.y);
+        }
+        Bounds::new(self.min_x.min(pos.x), self.min_y.min(pos.y), self.max_x.max(pos.x), self.max_y.max(pos.y))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min_x > self.max_x || self.min_y > self.max_y
+    }
+
+    pub fn width(&self) -> Scale {
+        (self.max_x - self.min_x + 1).max(0)
+    }
+
+    pub fn height(&self) -> Scale {
+        (self.max_y - self.min_y + 1).max(0)
+    }
+
+    pub fn area(&self) -> Scale {
+        self.width() * self.height()
+    }
+
+    pub fn columns(&self) -> RangeInclusive<Scale> {
+        self.min_x..=self.max_x
+    }
+
+    pub fn rows(&self) -> RangeInclusive<Scale> {
+        self.min_y..=self.max_y
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.columns().contains(&pos.x) && self.rows().contains(&pos.y)
+    }
+
+    ///Whether pos is on the edge, i.e. inside but with a neighbour that isn't
+    pub fn on_edge(&self, pos: Pos) -> bool {
+        self.contains(pos) && (pos.x == self.min_x || pos.x == self.max_x || pos.y == self.min_y || pos.y == self.max_y)
+    }
+
+    ///Bring a position that is off one edge back on at the opposite edge
+    pub fn wrap(&self, pos: Pos) -> Pos {
+        Pos::new(
+            self.min_x + (pos.x - self.min_x).rem_euclid(self.width()),
+            self.min_y + (pos.y - self.min_y).rem_euclid(self.height()),
+        )
+    }
+
+    ///Every position, in reading order
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let columns = self.columns();
+        self.rows().flat_map(move |y| columns.clone().map(move |x| Pos::new(x, y)))
+    }
+
+    ///Display something for each position, row by row, e.g. the grid with a path drawn on it
+    pub fn render<F, D>(self, cell: F) -> Render<F>
+    where
+        F: Fn(Pos) -> D,
+        D: Display,
+    {
+        Render { bounds: self, cell }
+    }
+}
+
+impl Display for Bounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}) -> ({}, {})", self.min_x, self.min_y, self.max_x, self.max_y)
+    }
+}
+
+///See [Bounds::render]
+pub struct Render<F> {
+    bounds: Bounds,
+    cell: F,
+}
+
+impl<F, D> Display for Render<F>
+where
+    F: Fn(Pos) -> D,
+    D: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in self.bounds.rows() {
+            for x in self.bounds.columns() {
+                write!(f, "{}", (self.cell)(Pos::new(x, y)))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+///What happens to a step off the edge of a grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wrap {
+    ///It leaves the grid
+    None,
+    ///It comes back on at the opposite edge
+    Around,
+}
+
+pub trait Grid<T> {
+    fn get(&self, pos: Pos) -> Option<&T>;
+
+    fn get_mut(&mut self, pos: Pos) -> Option<&mut T>;
+
+    ///The smallest bounds holding every cell
+    fn bounds(&self) -> Bounds;
+
+    fn contains(&self, pos: Pos) -> bool {
+        self.get(pos).is_some()
+    }
+
+    ///Where pos is within the bounds, None if it is outside them and isn't wrapped
+    fn locate(&self, pos: Pos, wrap: Wrap) -> Option<Pos> {
+        let bounds = self.bounds();
+        match wrap {
+            _ if bounds.contains(pos) => Some(pos),
+            Wrap::Around if !bounds.is_empty() => Some(bounds.wrap(pos)),
+            _ => None,
+        }
+    }
+
+    ///The positions north, east, south and west of pos that are within the bounds
+    fn neighbours4(&self, pos: Pos, wrap: Wrap) -> impl Iterator<Item = Pos> {
+        pos.neighbours4().into_iter().filter_map(move |neighbour| self.locate(neighbour, wrap))
+    }
+
+    ///The positions surrounding pos that are within the bounds
+    fn neighbours8(&self, pos: Pos, wrap: Wrap) -> impl Iterator<Item = Pos> {
+        pos.neighbours8().into_iter().filter_map(move |neighbour| self.locate(neighbour, wrap))
+    }
+}
+
+///A rectangular grid with a cell at every position, the top left being (0, 0)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DenseGrid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> DenseGrid<T> {
+    pub fn new() -> DenseGrid<T> {
+        DenseGrid { width: 0, height: 0, cells: Vec::new() }
+    }
+
+    pub fn filled(width: usize, height: usize, cell: T) -> DenseGrid<T>
+    where
+        T: Clone,
+    {
+        DenseGrid { width, height, cells: vec![cell; width * height] }
+    }
+
+    ///A grid with a cell for each of the text's chars, e.g. a map in the input. An error is returned
+    /// if the lines aren't all the same length.
+    pub fn parse<E>(text: &str, mut cell: impl FnMut(Pos, char) -> Result<T, E>) -> Result<DenseGrid<T>, E>
+    where
+        E: From<String>,
+    {
+        let mut grid = DenseGrid::new();
+        for (y, line) in text.lines().enumerate() {
+            let row = line
+                .chars()
+                .enumerate()
+                .map(|(x, c)| cell(Pos::new(x as Scale, y as Scale), c))
+                .collect::<Result<Vec<T>, E>>()?;
+            grid.try_push_row(row).map_err(|message| format!("line {}: {}", y + 1, message))?;
+        }
+        Ok(grid)
+    }
+
+    ///Add a row to the bottom, as the lines of a map are read, or an error if it isn't as wide as
+    /// the rows already there
+    pub fn try_push_row(&mut self, row: Vec<T>) -> Result<(), String> {
+        if self.height > 0 && row.len() != self.width {
+            return Err(format!("the row is {} long where the others are {}", row.len(), self.width));
+        }
+        self.width = row.len();
+        self.cells.extend(row);
+        self.height += 1;
+        Ok(())
+    }
+
+    ///As [DenseGrid::try_push_row], for rows already known to be as wide as the others (e.g. read
+    /// with [row_parser]). Panics if it isn't.
+    pub fn push_row(&mut self, row: Vec<T>) {
+        if let Err(message) = self.try_push_row(row) {
+            panic!("{}", message);
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        //chunks_exact doesn't allow a width of 0, and there are no rows to give then anyway
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    ///Each position with its cell, in reading order
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.bounds().positions().zip(self.cells.iter())
+    }
+
+    fn index_of(&self, pos: Pos) -> Option<usize> {
+        let (x, y) = (usize::try_from(pos.x).ok()?, usize::try_from(pos.y).ok()?);
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+}
+
+///A line parser for the rows of a [DenseGrid] read a line at a time, e.g. by
+/// [crate::utils::try_process_file], with a cell for each char. Unlike [DenseGrid::push_row] it
+/// gives an error rather than panicking when a row isn't as wide as the first.
+pub fn row_parser<T, E: Display>(mut cell: impl FnMut(char) -> Result<T, E>) -> impl FnMut(&str) -> Result<Vec<T>, String> {
+    let mut width = None;
+    move |line| {
+        let row = line.chars().map(|c| cell(c).map_err(|err| err.to_string())).collect::<Result<Vec<T>, String>>()?;
+        match *width.get_or_insert(row.len()) {
+            width if width != row.len() => Err(format!("the row is {} long where the first is {}", row.len(), width)),
+            _ => Ok(row),
+        }
+    }
+}
+
+impl<T> Default for DenseGrid<T> {
+    fn default() -> Self {
+        DenseGrid::new()
+    }
+}
+
+impl<T> Grid<T> for DenseGrid<T> {
+    fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|index| &self.cells[index])
+    }
+
+    fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(|index| &mut self.cells[index])
+    }
+
+    fn bounds(&self) -> Bounds {
+        Bounds::new(0, 0, self.width as Scale - 1, self.height as Scale - 1)
+    }
+}
+
+impl<T> Index<Pos> for DenseGrid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos).unwrap_or_else(|| panic!("{} is outside the {}x{} grid", pos, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Pos> for DenseGrid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos).unwrap_or_else(|| panic!("{} is outside the {}x{} grid", pos, width, height))
+    }
+}
+
+impl<T: Display> Display for DenseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.bounds().render(|pos| &self[pos]))
+    }
+}
+
+///A grid holding only some cells, anywhere on the plane
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Pos, T>,
+    ///Kept up to date as cells are added, None once a cell on the edge has been removed until
+    /// they are worked out again
+    bounds: Cell<Option<Bounds>>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid { cells: HashMap::new(), bounds: Cell::new(Some(Bounds::EMPTY)) }
+    }
+
+    ///A grid of the text's chars that cell gives a cell for, e.g. the '#'s in a map
+    pub fn parse<E>(text: &str, mut cell: impl FnMut(Pos, char) -> Result<Option<T>, E>) -> Result<SparseGrid<T>, E> {
+        let mut grid = SparseGrid::new();
+        for (y, line) in text.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let pos = Pos::new(x as Scale, y as Scale);
+                if let Some(cell) = cell(pos, c)? {
+                    grid.insert(pos, cell);
+                }
+            }
+        }
+        Ok(grid)
+    }
+
+    ///Put a cell at pos, returning the one that was there
+    pub fn insert(&mut self, pos: Pos, cell: T) -> Option<T> {
+        if let Some(bounds) = self.bounds.get() {
+            self.bounds.set(Some(bounds.including(pos)));
+        }
+        self.cells.insert(pos, cell)
+    }
+
+    pub fn remove(&mut self, pos: Pos) -> Option<T> {
+        let removed = self.cells.remove(&pos);
+        if removed.is_some() && self.bounds.get().is_some_and(|bounds| bounds.on_edge(pos)) {
+            self.bounds.set(None);
+        }
+        removed
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    ///Each position with its cell, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells.iter().map(|(pos, cell)| (*pos, cell))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + '_ {
+        self.cells.keys().copied()
+    }
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid::new()
+    }
+}
+
+impl<T> Grid<T> for SparseGrid<T> {
+    fn get(&self, pos: Pos) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    fn bounds(&self) -> Bounds {
+        self.bounds.get().unwrap_or_else(|| {
+            let bounds = Bounds::of(self.positions());
+            self.bounds.set(Some(bounds));
+            bounds
+        })
+    }
+}
+
+///Positions without a cell are shown as '.'
+impl<T: Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cell = |pos| self.get(pos).map_or(String::from("."), T::to_string);
+        write!(f, "{}", self.bounds().render(cell))
+    }
+}
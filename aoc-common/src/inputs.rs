@@ -0,0 +1,63 @@
+//Getting the puzzle inputs. Each day's input is kept in the year's directory as N/input.txt, which
+//is used if it is there, otherwise the input is fetched and saved there for next time.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::Error;
+use crate::runner::Day;
+use crate::website::Website;
+
+///Somewhere that puzzle inputs can be got from
+pub trait Fetcher {
+    fn fetch(&self, year: u16, day: Day) -> Result<String, Error>;
+}
+
+impl Fetcher for Website {
+    fn fetch(&self, year: u16, day: Day) -> Result<String, Error> {
+        self.get(&format!("/{}/day/{}/input", year, day))
+    }
+}
+
+pub struct InputProvider {
+    year: u16,
+    ///Holds a directory for each day, as a year's crate does
+    cache_directory: PathBuf,
+    ///None to only use what is already in the cache
+    fetcher: Option<Box<dyn Fetcher>>,
+}
+
+impl InputProvider {
+    ///A provider that only uses the inputs already in the cache
+    pub fn new(year: u16, cache_directory: &Path) -> InputProvider {
+        InputProvider { year, cache_directory: cache_directory.to_path_buf(), fetcher: None }
+    }
+
+    pub fn with_fetcher(mut self, fetcher: impl Fetcher + 'static) -> InputProvider {
+        self.fetcher = Some(Box::new(fetcher));
+        self
+    }
+
+    pub fn cached_path(&self, day: Day) -> PathBuf {
+        self.cache_directory.join(day.to_string()).join("input.txt")
+    }
+
+    ///The path to the day's input, fetching it into the cache first if it isn't there. An empty
+    /// file (as new-day leaves) counts as not being there.
+    pub fn input(&self, day: Day) -> Result<PathBuf, Error> {
+        let path = self.cached_path(day);
+        let name = path.display().to_string();
+        let cached = fs::metadata(&path).map(|metadata| metadata.len() > 0).unwrap_or(false);
+        if cached {
+            return Ok(path);
+        }
+        let Some(fetcher) = &self.fetcher else {
+            return Err(Error::format(&name, "not cached, and there is no session to fetch it with"));
+        };
+        let input = fetcher.fetch(self.year, day)?;
+        fs::create_dir_all(self.cache_directory.join(day.to_string()))
+            .and_then(|_| fs::write(&path, input))
+            .map_err(|err| Error::io(&name, None, err))?;
+        Ok(path)
+    }
+}
@@ -0,0 +1,17 @@
+//What every year's solutions share: getting, reading and parsing the input, the errors from doing
+//so, running, timing and reporting on the solutions, and submitting their answers.
+
+pub mod direction;
+pub mod error;
+pub mod grid;
+pub mod inputs;
+pub mod optimise;
+pub mod point;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod submit;
+pub mod timing;
+pub mod utils;
+pub mod website;
@@ -0,0 +1,142 @@
+//Finding the best outcome of a tree of choices, such as the order to do things in, when there are
+//far too many to try them all. Beam search is quick but may miss the best, branch and bound won't.
+
+use std::cmp::Ordering;
+
+///A problem where choices are made one after another from a start state to get the highest score
+pub trait Problem {
+    type State: Clone;
+    type Score: Copy + Ord;
+
+    ///The states that one more choice leads to, none when there are no choices left
+    fn successors(&self, state: &Self::State) -> Vec<Self::State>;
+
+    ///The score of making no more choices from state
+    fn score(&self, state: &Self::State) -> Self::Score;
+
+    ///A score that nothing reachable from state can beat. The lower it is the more can be pruned,
+    /// but if it is ever too low the best may be missed.
+    fn bound(&self, state: &Self::State) -> Self::Score;
+
+    ///Orders the more promising of two states first, by default the one with the higher bound
+    fn compare(&self, a: &Self::State, b: &Self::State) -> Ordering {
+        self.bound(b).cmp(&self.bound(a))
+    }
+}
+
+///How much work an optimisation did
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct OptimiseStats {
+    ///States whose successors were looked at
+    pub expanded: usize,
+    ///States left alone as their bound couldn't beat the best score found
+    pub pruned: usize,
+    ///States a beam had no room for
+    pub dropped: usize,
+}
+
+///The best state found and its score
+#[derive(Debug, Clone)]
+pub struct Best<S, V> {
+    pub score: V,
+    pub state: S,
+    ///Whether it is known that no state scores more. A beam search can only tell if nothing it
+    /// dropped could have.
+    pub proven_optimal: bool,
+    pub stats: OptimiseStats,
+}
+
+///Looks at the states a choice at a time, keeping only the width most promising (by
+/// [Problem::compare]) after each choice
+pub fn beam_search<P: Problem>(problem: &P, start: P::State, width: usize) -> Best<P::State, P::Score> {
+    let mut best = Incumbent::new(problem, start.clone());
+    //The highest bound of anything dropped, which the best has to reach to be proven
+    let mut dropped_bound: Option<P::Score> = None;
+    let mut beam = vec![start];
+    while !beam.is_empty() {
+        let mut next_beam = Vec::new();
+        for state in beam.iter() {
+            best.stats.expanded += 1;
+            for next in problem.successors(state) {
+                best.offer(problem, &next);
+                next_beam.push(next);
+            }
+        }
+        next_beam.retain(|state| !best.prune(problem, state));
+        if next_beam.len() > width {
+            next_beam.sort_by(|a, b| problem.compare(a, b));
+            for dropped in next_beam.drain(width..) {
+                let bound = problem.bound(&dropped);
+                dropped_bound = dropped_bound.max(Some(bound));
+                best.stats.dropped += 1;
+            }
+        }
+        beam = next_beam;
+    }
+    let proven_optimal = dropped_bound.is_none_or(|bound| bound <= best.score);
+    best.finish(proven_optimal)
+}
+
+///Looks at every state depth first, the most promising choices first, skipping any whose bound
+/// can't beat the best score found so far
+pub fn branch_and_bound<P: Problem>(problem: &P, start: P::State) -> Best<P::State, P::Score> {
+    let mut best = Incumbent::new(problem, start.clone());
+    let mut stack = vec![start];
+    while let Some(state) = stack.pop() {
+        //The best may have improved since it was stacked
+        if best.prune(problem, &state) {
+            continue;
+        }
+        best.stats.expanded += 1;
+        let mut successors = problem.successors(&state);
+        //Least promising first, so the most promising is on the top of the stack
+        successors.sort_by(|a, b| problem.compare(b, a));
+        for next in successors {
+            best.offer(problem, &next);
+            stack.push(next);
+        }
+    }
+    best.finish(true)
+}
+
+///The best found so far
+struct Incumbent<S, V> {
+    score: V,
+    state: S,
+    stats: OptimiseStats,
+}
+
+impl<S: Clone, V: Copy + Ord> Incumbent<S, V> {
+    fn new<P: Problem<State = S, Score = V>>(problem: &P, start: S) -> Incumbent<S, V> {
+        Incumbent {
+            score: problem.score(&start),
+            state: start,
+            stats: OptimiseStats::default(),
+        }
+    }
+
+    fn offer<P: Problem<State = S, Score = V>>(&mut self, problem: &P, state: &S) {
+        let score = problem.score(state);
+        if score > self.score {
+            self.score = score;
+            self.state = state.clone();
+        }
+    }
+
+    fn prune<P: Problem<State = S, Score = V>>(&mut self, problem: &P, state: &S) -> bool {
+        let pruned = problem.bound(state) <= self.score;
+        if pruned {
+            self.stats.pruned += 1;
+        }
+        pruned
+    }
+
+    fn finish(self, proven_optimal: bool) -> Best<S, V> {
+        Best {
+            score: self.score,
+            state: self.state,
+            proven_optimal,
+            stats: self.stats,
+        }
+    }
+}
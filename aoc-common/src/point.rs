@@ -0,0 +1,224 @@
+//Points in two and three dimensions, which double as the vectors between them. Following the
+//inputs, y increases going down the page, which is what north, left and right are in terms of.
+
+use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::direction::{Direction4, Direction8};
+
+///The signed integers that points are made of
+pub trait Scalar:
+    Copy + Ord + Hash + Default + Debug + Display + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs(self) -> Self;
+
+    fn signum(self) -> Self;
+}
+
+macro_rules! scalar {
+    ($($t:ty),*) => {
+        $(impl Scalar for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+
+            fn signum(self) -> Self {
+                <$t>::signum(self)
+            }
+        })*
+    };
+}
+
+scalar!(i8, i16, i32, i64, i128, isize);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Point2<T> {
+        Point2 { x, y }
+    }
+}
+
+impl<T: Scalar> Point2<T> {
+    pub const ORIGIN: Point2<T> = Point2::new(T::ZERO, T::ZERO);
+
+    pub fn manhattan(&self, other: Point2<T>) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    ///Each of the coordinates replaced with its sign, e.g. to step once towards a point
+    pub fn signum(&self) -> Point2<T> {
+        Point2::new(self.x.signum(), self.y.signum())
+    }
+
+    ///A quarter turn about the origin, east becoming north
+    pub fn rotate_left(&self) -> Point2<T> {
+        Point2::new(self.y, -self.x)
+    }
+
+    ///A quarter turn about the origin, east becoming south
+    pub fn rotate_right(&self) -> Point2<T> {
+        Point2::new(-self.y, self.x)
+    }
+
+    ///The point one step away in the direction
+    pub fn step(&self, direction: Direction4) -> Point2<T> {
+        *self + direction.offset()
+    }
+
+    ///The points north, east, south and west of this one, in that order
+    pub fn neighbours4(&self) -> [Point2<T>; 4] {
+        Direction4::ALL.map(|direction| self.step(direction))
+    }
+
+    ///The points surrounding this one, clockwise from north
+    pub fn neighbours8(&self) -> [Point2<T>; 8] {
+        Direction8::ALL.map(|direction| *self + direction.offset())
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point2<T> {
+    type Output = Point2<T>;
+
+    fn add(self, other: Point2<T>) -> Point2<T> {
+        Point2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Point2<T>;
+
+    fn sub(self, other: Point2<T>) -> Point2<T> {
+        Point2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point2<T> {
+    type Output = Point2<T>;
+
+    fn mul(self, scale: T) -> Point2<T> {
+        Point2::new(self.x * scale, self.y * scale)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point2<T> {
+    type Output = Point2<T>;
+
+    fn neg(self) -> Point2<T> {
+        Point2::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Add<Output = T> + Copy> AddAssign for Point2<T> {
+    fn add_assign(&mut self, other: Point2<T>) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Sub<Output = T> + Copy> SubAssign for Point2<T> {
+    fn sub_assign(&mut self, other: Point2<T>) {
+        *self = *self - other;
+    }
+}
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Point3<T> {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Scalar> Point3<T> {
+    pub const ORIGIN: Point3<T> = Point3::new(T::ZERO, T::ZERO, T::ZERO);
+
+    pub fn manhattan(&self, other: Point3<T>) -> T {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+
+    ///The points sharing a face with this one, going either way along x, then y, then z
+    pub fn neighbours6(&self) -> [Point3<T>; 6] {
+        let (one, zero) = (T::ONE, T::ZERO);
+        [
+            Point3::new(one, zero, zero),
+            Point3::new(-one, zero, zero),
+            Point3::new(zero, one, zero),
+            Point3::new(zero, -one, zero),
+            Point3::new(zero, zero, one),
+            Point3::new(zero, zero, -one),
+        ]
+        .map(|offset| *self + offset)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Point3<T>;
+
+    fn add(self, other: Point3<T>) -> Point3<T> {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Point3<T>;
+
+    fn sub(self, other: Point3<T>) -> Point3<T> {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point3<T> {
+    type Output = Point3<T>;
+
+    fn mul(self, scale: T) -> Point3<T> {
+        Point3::new(self.x * scale, self.y * scale, self.z * scale)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Point3<T>;
+
+    fn neg(self) -> Point3<T> {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: Add<Output = T> + Copy> AddAssign for Point3<T> {
+    fn add_assign(&mut self, other: Point3<T>) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Sub<Output = T> + Copy> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, other: Point3<T>) {
+        *self = *self - other;
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
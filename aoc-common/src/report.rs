@@ -0,0 +1,109 @@
+//Machine readable reports of the runs, one record per solution run, for scripts and dashboards
+//to consume rather than scraping the text output.
+
+use std::io::Write;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::runner::{Day, Outcome, Run};
+use crate::timing::Stats;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    ///Answers as they are found, then a table of timings
+    Text,
+    ///An array of records
+    Json,
+    ///A header line and then a line per record
+    Csv,
+}
+
+///What is reported for each solution, times are in seconds. Timings are only given for solved
+/// runs, with min and max the same as the median unless the solution was benchmarked.
+#[derive(Debug, Serialize)]
+pub struct Record<'a> {
+    pub day: Day,
+    pub part: String,
+    pub input: &'a str,
+    ///solved, failed or skipped
+    pub status: &'static str,
+    pub answer: Option<&'a str>,
+    pub answer_type: Option<&'static str>,
+    ///Why the solution failed or was skipped
+    pub error: Option<String>,
+    pub runs: usize,
+    pub total_min: Option<f64>,
+    pub total_median: Option<f64>,
+    pub total_max: Option<f64>,
+    ///The median time spent in the parse/accumulate and reduce phases, if the solution has them
+    pub parse_median: Option<f64>,
+    pub reduce_median: Option<f64>,
+}
+
+impl<'a> Record<'a> {
+    pub fn new(run: &'a Run) -> Record<'a> {
+        let mut record = Record {
+            day: run.solution.day,
+            part: run.solution.part.to_string(),
+            input: &run.input,
+            status: "solved",
+            answer: None,
+            answer_type: None,
+            error: None,
+            runs: 0,
+            total_min: None,
+            total_median: None,
+            total_max: None,
+            parse_median: None,
+            reduce_median: None,
+        };
+        match &run.outcome {
+            Outcome::Solved { answer, timings } => {
+                record.answer = Some(&answer.value);
+                record.answer_type = Some(answer.type_name);
+                record.runs = timings.len();
+                if let Some(total) = Stats::of(timings.iter().map(|timing| timing.total)) {
+                    record.total_min = Some(total.min.as_secs_f64());
+                    record.total_median = Some(total.median.as_secs_f64());
+                    record.total_max = Some(total.max.as_secs_f64());
+                }
+                let phases = timings.iter().filter_map(|timing| timing.phases);
+                record.parse_median = Stats::of(phases.clone().map(|phases| phases.parse))
+                    .map(|parse| parse.median.as_secs_f64());
+                record.reduce_median = Stats::of(phases.map(|phases| phases.reduce))
+                    .map(|reduce| reduce.median.as_secs_f64());
+            }
+            Outcome::Failed(error) => {
+                record.status = "failed";
+                record.error = Some(error.to_string());
+            }
+            Outcome::Skipped(reason) => {
+                record.status = "skipped";
+                record.error = Some(String::from(*reason));
+            }
+        }
+        record
+    }
+}
+
+///Write a record for each of the runs in the format, which must be Json or Csv
+pub fn write(writer: impl Write, format: Format, runs: &[Run]) -> Result<(), Box<dyn std::error::Error>> {
+    let records: Vec<Record> = runs.iter().map(Record::new).collect();
+    match format {
+        Format::Json => {
+            let mut writer = writer;
+            serde_json::to_writer_pretty(&mut writer, &records)?;
+            writeln!(writer)?;
+        }
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(writer);
+            for record in records {
+                writer.serialize(record)?;
+            }
+            writer.flush()?;
+        }
+        Format::Text => return Err("text isn't a report format".into()),
+    }
+    Ok(())
+}
@@ -0,0 +1,308 @@
+use std::fmt::{self, Display};
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+use clap::ValueEnum;
+
+use crate::error::Error;
+use crate::scaffold::Layout;
+use crate::timing::{self, Phases, Stats, Timing};
+use crate::utils::{self, Input};
+
+pub type Day = u8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Part {
+    A,
+    B,
+    ///Both parts come out of a single function (e.g. day 10 draws part b while summing part a)
+    #[value(skip)]
+    Both,
+}
+
+impl Part {
+    pub fn matches(&self, wanted: Option<Part>) -> bool {
+        match (self, wanted) {
+            (_, None) | (Part::Both, Some(_)) => true,
+            (part, Some(wanted)) => *part == wanted,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let part = match self {
+            Part::A => "a",
+            Part::B => "b",
+            Part::Both => "a+b",
+        };
+        write!(f, "{}", part)
+    }
+}
+
+///An answer along with the type that the solution gave it as, e.g. i64 or String
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub value: String,
+    pub type_name: &'static str,
+}
+
+impl Answer {
+    pub fn new<T: Display>(value: T) -> Answer {
+        let type_name = std::any::type_name::<T>();
+        Answer {
+            value: value.to_string(),
+            //Just String rather than alloc::string::String
+            type_name: type_name.rsplit("::").next().unwrap_or(type_name),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+pub enum Solver {
+    Function(fn(Input) -> Result<Answer, Error>),
+    ///There is nothing to run, the reason is given
+    NotAvailable(&'static str),
+}
+
+pub struct Solution {
+    pub day: Day,
+    pub part: Part,
+    pub name: &'static str,
+    pub solver: Solver,
+}
+
+impl Solution {
+    pub fn label(&self) -> String {
+        format!("{}{}", self.day, self.part)
+    }
+
+    ///Work out the file to read given an optional override:
+    /// - no override reads the day's input.txt
+    /// - a bare file name, e.g. test_input.txt, is looked for in the day's directory
+    /// - anything else, including [utils::STDIN], is used as it is
+    pub fn input_file_name(&self, input: Option<&str>) -> String {
+        match input {
+            None => format!("{}/input.txt", self.day),
+            Some(utils::STDIN) => String::from(utils::STDIN),
+            Some(name) if Path::new(name).components().count() == 1 => format!("{}/{}", self.day, name),
+            Some(path) => String::from(path),
+        }
+    }
+}
+
+///Register a day's solver in a year's solutions, e.g. `solution!(1, A, one::_1a)`
+#[macro_export]
+macro_rules! solution {
+    ($day:expr, $part:ident, $func:path) => {
+        $crate::runner::Solution {
+            day: $day,
+            part: $crate::runner::Part::$part,
+            name: stringify!($func),
+            solver: $crate::runner::Solver::Function(|input| $func(input).map($crate::runner::Answer::new)),
+        }
+    };
+}
+
+///A year's solutions and where the inputs for its days are
+pub struct Year {
+    pub year: u16,
+    ///The directory holding each day's directory of inputs
+    pub directory: &'static str,
+    pub solutions: &'static [Solution],
+    ///Where new-day finds the files in the directory that it adds a day to
+    pub layout: Layout,
+}
+
+///An inclusive range of days, parsed from "17", "1..10" or "1..=10"
+#[derive(Debug, Clone)]
+pub struct Days(RangeInclusive<Day>);
+
+impl Days {
+    pub fn all() -> Days {
+        Days(1..=25)
+    }
+
+    pub fn contains(&self, day: Day) -> bool {
+        self.0.contains(&day)
+    }
+}
+
+impl FromStr for Days {
+    type Err = String;
+
+    ///Days run from 1 to 25, the end of an exclusive range can be 26
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn parse_day(s: &str) -> Result<Day, String> {
+            s.trim().parse().map_err(|_| format!("'{}' is not a day number", s))
+        }
+
+        let days = if let Some((start, end)) = s.split_once("..=") {
+            parse_day(start)?..=parse_day(end)?
+        } else if let Some((start, end)) = s.split_once("..") {
+            let end = parse_day(end)?;
+            if end == 0 {
+                return Err(format!("'{}' is an empty range", s));
+            }
+            parse_day(start)?..=(end - 1)
+        } else {
+            let day = parse_day(s)?;
+            day..=day
+        };
+        if days.is_empty() {
+            return Err(format!("'{}' is an empty range", s));
+        }
+        if *days.start() < 1 || *days.end() > 25 {
+            return Err(format!("'{}' goes outside the days, they run from 1 to 25", s));
+        }
+        Ok(Days(days))
+    }
+}
+
+///Find the solutions on any of the days, optionally restricted to a single part
+pub fn select(solutions: &'static [Solution], days: &[Days], part: Option<Part>) -> Vec<&'static Solution> {
+    solutions
+        .iter()
+        .filter(|solution| days.iter().any(|days| days.contains(solution.day)))
+        .filter(|solution| solution.part.matches(part))
+        .collect()
+}
+
+///What running a solution came to
+pub enum Outcome {
+    ///The answer, with a timing for each run
+    Solved { answer: Answer, timings: Vec<Timing> },
+    Failed(Error),
+    Skipped(&'static str),
+}
+
+///A solution that has been run against its input
+pub struct Run<'a> {
+    pub solution: &'a Solution,
+    pub input: String,
+    pub outcome: Outcome,
+}
+
+impl Run<'_> {
+    pub fn failed(&self) -> bool {
+        matches!(self.outcome, Outcome::Failed(_))
+    }
+}
+
+///Run each of the solutions, runs times each to benchmark them. on_run is called as each
+/// solution finishes, e.g. to print its answer while the rest are running.
+pub fn run<'a>(solutions: &[&'a Solution], input: Option<&str>, runs: usize, mut on_run: impl FnMut(&Run)) -> Vec<Run<'a>> {
+    let mut results = Vec::new();
+    for solution in solutions {
+        let file_name = solution.input_file_name(input);
+        let outcome = match &solution.solver {
+            Solver::Function(solve) => run_solver(solve, &file_name, runs),
+            Solver::NotAvailable(reason) => Outcome::Skipped(reason),
+        };
+        let run = Run { solution, input: file_name, outcome };
+        on_run(&run);
+        results.push(run);
+    }
+    results
+}
+
+///Run the solver, stopping at the first failure. The answer is taken from the first run.
+fn run_solver(solve: &fn(Input) -> Result<Answer, Error>, file_name: &str, runs: usize) -> Outcome {
+    let (answer, mut timings) = match timing::measure(|| solve(Input::File(file_name))) {
+        (Ok(answer), timing) => (answer, vec![timing]),
+        (Err(error), _) => return Outcome::Failed(error),
+    };
+    for _ in 1..runs {
+        match timing::measure(|| solve(Input::File(file_name))) {
+            (Ok(_), timing) => timings.push(timing),
+            (Err(error), _) => return Outcome::Failed(error),
+        }
+    }
+    Outcome::Solved { answer, timings }
+}
+
+///Print the outcome of a run as text
+pub fn print_run(run: &Run) {
+    let label = run.solution.label();
+    match &run.outcome {
+        Outcome::Solved { answer, .. } => println!("{} Result is: {}", label, answer),
+        Outcome::Failed(error) => eprintln!("{} Failed: {}", label, error),
+        Outcome::Skipped(reason) => println!("{} Skipped: {}", label, reason),
+    }
+}
+
+///Print a table of how long the solutions took, with min/median/max if they were run more than once
+pub fn print_timings(runs: &[Run]) {
+    let timings: Vec<(&Solution, &[Timing])> = runs
+        .iter()
+        .filter_map(|run| match &run.outcome {
+            Outcome::Solved { timings, .. } => Some((run.solution, timings.as_slice())),
+            _ => None,
+        })
+        .collect();
+    if timings.is_empty() {
+        return;
+    }
+    println!();
+    if timings.iter().any(|(_, timings)| timings.len() > 1) {
+        print_bench_table(&timings);
+    } else {
+        print_timing_table(&timings);
+    }
+}
+
+///"-" for a phase time when the solution doesn't use process_file
+fn format_phase(phases: Option<Phases>, phase: impl Fn(&Phases) -> Duration) -> String {
+    phases.as_ref().map_or(String::from("-"), |phases| timing::format_duration(phase(phases)))
+}
+
+fn print_timing_table(timings: &[(&Solution, &[Timing])]) {
+    println!("{:<8} {:>12} {:>12} {:>12}", "Solution", "Parse", "Reduce", "Total");
+    let mut total = Duration::ZERO;
+    for (solution, solution_timings) in timings {
+        for timing in solution_timings.iter() {
+            println!(
+                "{:<8} {:>12} {:>12} {:>12}",
+                solution.label(),
+                format_phase(timing.phases, |phases| phases.parse),
+                format_phase(timing.phases, |phases| phases.reduce),
+                timing::format_duration(timing.total)
+            );
+            total += timing.total;
+        }
+    }
+    println!("{:<8} {:>12} {:>12} {:>12}", "All", "", "", timing::format_duration(total));
+}
+
+fn print_bench_table(timings: &[(&Solution, &[Timing])]) {
+    println!(
+        "{:<8} {:>5} {:>12} {:>12} {:>12} {:>14} {:>14}",
+        "Solution", "Runs", "Min", "Median", "Max", "Median parse", "Median reduce"
+    );
+    for (solution, solution_timings) in timings {
+        let Some(total) = Stats::of(solution_timings.iter().map(|timing| timing.total)) else {
+            continue;
+        };
+        let phases = solution_timings.iter().filter_map(|timing| timing.phases);
+        let parse = Stats::of(phases.clone().map(|phases| phases.parse));
+        let reduce = Stats::of(phases.map(|phases| phases.reduce));
+        let median = |stats: Option<Stats>| stats.map_or(String::from("-"), |stats| timing::format_duration(stats.median));
+        println!(
+            "{:<8} {:>5} {:>12} {:>12} {:>12} {:>14} {:>14}",
+            solution.label(),
+            solution_timings.len(),
+            timing::format_duration(total.min),
+            timing::format_duration(total.median),
+            timing::format_duration(total.max),
+            median(parse),
+            median(reduce)
+        );
+    }
+}
@@ -0,0 +1,233 @@
+//Starting a new day: the module is generated from the year's template and registered everywhere
+//that the days are listed, and the day's input files are created ready to be filled in.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::error::Error;
+use crate::runner::{Day, Part};
+
+///Module names for the days, the modules are named after the day spelt out
+const DAY_NAMES: [&str; 25] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven",
+    "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen",
+    "twenty", "twentyone", "twentytwo", "twentythree", "twentyfour", "twentyfive",
+];
+
+///The answer given in expected_answers.txt until the real one is known, these aren't checked
+pub const TODO_ANSWER: &str = "TODO";
+
+///How long a line of the runner's imports can be before it is wrapped (as rustfmt would)
+const MAX_WIDTH: usize = 100;
+
+///The files of a year's crate that new-day reads and adds the day to, relative to the crate
+pub struct Layout {
+    ///The module that each new day starts as a copy of
+    pub template: &'static str,
+    ///Declares the day modules
+    pub lib: &'static str,
+    ///Has the `use crate::{...};` of the day modules and the SOLUTIONS registering them
+    pub runner: &'static str,
+    ///Has the `day_tests!` list of days checked against the expected answers
+    pub regression: &'static str,
+    ///The answers that the regression tests check each day against
+    pub expected_answers: &'static str,
+}
+
+///Create the day in the crate at root, returning the files that were created or changed
+pub fn new_day(root: &Path, layout: &Layout, day: Day) -> Result<Vec<PathBuf>, Error> {
+    let name = *DAY_NAMES
+        .get(usize::from(day).wrapping_sub(1))
+        .ok_or_else(|| Error::format(&day.to_string(), "days run from 1 to 25"))?;
+    //The day's module goes next to the template
+    let module = root.join(layout.template).with_file_name(format!("{}.rs", name));
+    if module.exists() {
+        return Err(Error::format(&display(&module), format!("day {} already exists", day)));
+    }
+
+    let template = read(&root.join(layout.template))?;
+    write(&module, &template
+        .replace("_template_a", &format!("_{}a", day))
+        .replace("_template_b", &format!("_{}b", day)))?;
+    let mut changed = vec![module];
+
+    changed.push(update(&root.join(layout.lib), |lib| add_module(lib, name))?);
+    let runner = root.join(layout.runner);
+    let parts = unregistered_parts(&read(&runner)?, day).map_err(|message| Error::format(&display(&runner), message))?;
+    changed.push(update(&runner, |runner| {
+        add_import(runner, name).and_then(|runner| add_solutions(&runner, day, name, &parts))
+    })?);
+    changed.push(update(&root.join(layout.regression), |regression| add_day_test(regression, day))?);
+
+    let expected_answers = root.join(layout.expected_answers);
+    let listed = read(&expected_answers)?;
+    let todo: String = parts
+        .iter()
+        .filter(|part| !listed.lines().any(|line| line.starts_with(&format!("{} {} test_input.txt ", day, part))))
+        .map(|part| format!("{} {} test_input.txt {}\n", day, part, TODO_ANSWER))
+        .collect();
+    if !todo.is_empty() {
+        append(&expected_answers, &format!("\n{}", todo))?;
+        changed.push(expected_answers);
+    }
+
+    let directory = root.join(day.to_string());
+    fs::create_dir_all(&directory).map_err(|err| Error::io(&display(&directory), None, err))?;
+    for input in ["input.txt", "test_input.txt"] {
+        let input = directory.join(input);
+        if !input.exists() {
+            write(&input, "")?;
+            changed.push(input);
+        }
+    }
+    Ok(changed)
+}
+
+///`pub mod name;` goes before the first module that sorts after it
+fn add_module(lib: &str, name: &str) -> Result<String, String> {
+    let line = format!("pub mod {};", name);
+    let mut lines: Vec<&str> = lib.lines().collect();
+    let modules: Vec<usize> = (0..lines.len()).filter(|index| lines[*index].starts_with("pub mod ")).collect();
+    let last = *modules.last().ok_or("no pub mod lines to add the day to")?;
+    let index = modules.into_iter().find(|index| lines[*index] > line.as_str()).unwrap_or(last + 1);
+    lines.insert(index, &line);
+    Ok(lines.join("\n") + "\n")
+}
+
+///Add the module to the runner's `use crate::{...};`, keeping it sorted and wrapped
+fn add_import(runner: &str, name: &str) -> Result<String, String> {
+    const START: &str = "use crate::{\n";
+    let start = runner.find(START).ok_or("no use crate::{...} to add the day to")? + START.len();
+    let end = start + runner[start..].find("};").ok_or("use crate::{ isn't closed")?;
+    let mut names: Vec<&str> = runner[start..end].split(',').map(str::trim).filter(|name| !name.is_empty()).collect();
+    names.push(name);
+    names.sort_unstable();
+
+    let mut imports = String::new();
+    let mut line = String::from("   ");
+    for name in names {
+        if line.len() + name.len() + 2 > MAX_WIDTH {
+            imports.push_str(&line);
+            imports.push('\n');
+            line = String::from("   ");
+        }
+        line.push_str(&format!(" {},", name));
+    }
+    imports.push_str(&line);
+    imports.push('\n');
+    Ok(format!("{}{}{}", &runner[..start], imports, &runner[end..]))
+}
+
+///The parts of the day that SOLUTIONS doesn't have an entry for yet
+fn unregistered_parts(runner: &str, day: Day) -> Result<Vec<Part>, String> {
+    let lines: Vec<String> = runner.lines().map(String::from).collect();
+    let entries = solution_entries(&lines[solutions_start(&lines)? + 1..]);
+    Ok([Part::A, Part::B]
+        .into_iter()
+        .filter(|part| !entries.iter().any(|(entry_day, entry_part, _)| *entry_day == day && entry_part.matches(Some(*part))))
+        .collect())
+}
+
+fn solutions_start(lines: &[String]) -> Result<usize, String> {
+    lines
+        .iter()
+        .position(|line| line.starts_with("pub const SOLUTIONS"))
+        .ok_or(String::from("no SOLUTIONS to add the day to"))
+}
+
+///Register the parts in SOLUTIONS after the days before it
+fn add_solutions(runner: &str, day: Day, name: &str, parts: &[Part]) -> Result<String, String> {
+    let mut lines: Vec<String> = runner.lines().map(String::from).collect();
+    let start = solutions_start(&lines)?;
+    let index = start + 1 + solution_entries(&lines[start + 1..])
+        .iter()
+        .filter(|(entry_day, _, _)| *entry_day < day)
+        .map(|(_, _, end)| end + 1)
+        .max()
+        .unwrap_or(0);
+    for part in parts.iter().rev() {
+        let variant = format!("{:?}", part);
+        lines.insert(index, format!("    solution!({}, {}, {}::_{}{}),", day, variant, name, day, part));
+    }
+    Ok(lines.join("\n") + "\n")
+}
+
+///The day and part of each entry in the SOLUTIONS lines, with the index of the entry's last line.
+/// Entries are either `solution!(day, part, ...)` or a `Solution { ... }` written out in full.
+fn solution_entries(lines: &[String]) -> Vec<(Day, Part, usize)> {
+    let mut entries = Vec::new();
+    let (mut day, mut part) = (None, None);
+    for (index, line) in lines.iter().enumerate() {
+        let line = line.trim();
+        if line == "];" {
+            break;
+        }
+        if let Some(args) = line.strip_prefix("solution!(") {
+            let mut args = args.split(',').map(str::trim);
+            if let (Some(Ok(day)), Some(Some(part))) = (args.next().map(str::parse), args.next().map(parse_part)) {
+                entries.push((day, part, index));
+            }
+        } else if let Some(value) = line.strip_prefix("day:") {
+            day = value.trim().trim_end_matches(',').parse().ok();
+        } else if let Some(value) = line.strip_prefix("part:") {
+            part = parse_part(value.trim().trim_end_matches(',').trim_start_matches("Part::"));
+        } else if line == "}," {
+            if let (Some(day), Some(part)) = (day.take(), part.take()) {
+                entries.push((day, part, index));
+            }
+        }
+    }
+    entries
+}
+
+fn parse_part(part: &str) -> Option<Part> {
+    match part {
+        "A" => Some(Part::A),
+        "B" => Some(Part::B),
+        "Both" => Some(Part::Both),
+        _ => None,
+    }
+}
+
+///Add `day_NN: N,` to the day_tests! list, unless there is already a test for the day
+fn add_day_test(regression: &str, day: Day) -> Result<String, String> {
+    let test = format!("    day_{:02}: {},", day, day);
+    let mut lines: Vec<&str> = regression.lines().collect();
+    if lines.contains(&test.as_str()) {
+        return Ok(regression.to_string());
+    }
+    let start = lines.iter().position(|line| *line == "day_tests! {").ok_or("no day_tests! to add the day to")?;
+    let end = start + lines[start..].iter().position(|line| *line == "}").ok_or("day_tests! isn't closed")?;
+    let index = (start + 1..end).find(|index| lines[*index] > test.as_str()).unwrap_or(end);
+    lines.insert(index, &test);
+    Ok(lines.join("\n") + "\n")
+}
+
+fn display(path: &Path) -> String {
+    path.display().to_string()
+}
+
+fn read(path: &Path) -> Result<String, Error> {
+    fs::read_to_string(path).map_err(|err| Error::io(&display(path), None, err))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), Error> {
+    fs::write(path, contents).map_err(|err| Error::io(&display(path), None, err))
+}
+
+fn append(path: &Path, contents: &str) -> Result<(), Error> {
+    OpenOptions::new()
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .map_err(|err| Error::io(&display(path), None, err))
+}
+
+///Rewrite the file with the edit, the message of a failed edit says where it couldn't be made
+fn update(path: &Path, edit: impl FnOnce(&str) -> Result<String, String>) -> Result<PathBuf, Error> {
+    let contents = read(path)?;
+    let updated = edit(&contents).map_err(|message| Error::format(&display(path), message))?;
+    write(path, &updated)?;
+    Ok(path.to_path_buf())
+}
@@ -0,0 +1,165 @@
+//Searching a graph for the shortest path from a start node to a goal. The graph is never built, it
+//is given by a function from a node to the nodes that can be reached from it in one step.
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+///What the cost of a step can be, the default being a cost of nothing
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<C: Copy + Ord + Add<Output = C> + Default> Cost for C {}
+
+///How much work a search did
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SearchStats {
+    ///Nodes whose successors were looked at
+    pub expanded: usize,
+    ///Different nodes that were reached
+    pub discovered: usize,
+    ///The most nodes that were waiting to be expanded at once
+    pub max_frontier: usize,
+}
+
+///The shortest path found to a goal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub cost: C,
+    ///From the start to the goal, both included
+    pub nodes: Vec<N>,
+    pub stats: SearchStats,
+}
+
+impl<N, C> Path<N, C> {
+    pub fn goal(&self) -> &N {
+        self.nodes.last().expect("a path has at least its start")
+    }
+}
+
+///Breadth first search, for when each step costs the same. The cost is the number of steps.
+pub fn bfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I, mut is_goal: impl FnMut(&N) -> bool) -> Option<Path<N, usize>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut stats = SearchStats { discovered: 1, ..SearchStats::default() };
+    let mut parents: HashMap<N, Option<N>> = HashMap::from([(start.clone(), None)]);
+    let mut frontier = VecDeque::from([(start, 0)]);
+    while let Some((node, steps)) = frontier.pop_front() {
+        if is_goal(&node) {
+            return Some(Path { cost: steps, nodes: reconstruct(&parents, node), stats });
+        }
+        stats.expanded += 1;
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = parents.entry(next.clone()) {
+                entry.insert(Some(node.clone()));
+                frontier.push_back((next, steps + 1));
+                stats.discovered += 1;
+            }
+        }
+        stats.max_frontier = stats.max_frontier.max(frontier.len());
+    }
+    None
+}
+
+///Every node that can be reached from the start, with the fewest steps it takes to get there
+pub fn reachable<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut steps = HashMap::from([(start.clone(), 0)]);
+    let mut frontier = VecDeque::from([(start, 0)]);
+    while let Some((node, node_steps)) = frontier.pop_front() {
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = steps.entry(next.clone()) {
+                entry.insert(node_steps + 1);
+                frontier.push_back((next, node_steps + 1));
+            }
+        }
+    }
+    steps
+}
+
+///Dijkstra's algorithm, successors gives each node that can be reached along with the cost of the step
+pub fn dijkstra<N, C, I>(start: N, successors: impl FnMut(&N) -> I, is_goal: impl FnMut(&N) -> bool) -> Option<Path<N, C>>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+///A*, which is Dijkstra's algorithm looking at the nodes that seem closest to a goal first. The
+/// heuristic estimates the cost from a node to the nearest goal, and must never overestimate it for
+/// the path found to be the shortest.
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    //The nodes are numbered as they are found so that the frontier doesn't need them to be Ord
+    let mut nodes = vec![start.clone()];
+    let mut numbers = HashMap::from([(start.clone(), 0)]);
+    //The cheapest way found to each node so far, by number: its cost and the node before it
+    let mut best: Vec<(C, Option<usize>)> = vec![(C::default(), None)];
+    let mut frontier = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+    let mut stats = SearchStats { discovered: 1, ..SearchStats::default() };
+    while let Some(Reverse((_, cost, number))) = frontier.pop() {
+        if cost > best[number].0 {
+            //Already expanded more cheaply
+            continue;
+        }
+        let node = nodes[number].clone();
+        if is_goal(&node) {
+            let mut path = vec![number];
+            while let Some(previous) = best[*path.last().unwrap()].1 {
+                path.push(previous);
+            }
+            let nodes = path.into_iter().rev().map(|number| nodes[number].clone()).collect();
+            return Some(Path { cost, nodes, stats });
+        }
+        stats.expanded += 1;
+        for (next, step_cost) in successors(&node) {
+            let next_cost = cost + step_cost;
+            let next_number = match numbers.entry(next) {
+                Entry::Occupied(entry) => {
+                    let next_number = *entry.get();
+                    if next_cost >= best[next_number].0 {
+                        continue;
+                    }
+                    best[next_number] = (next_cost, Some(number));
+                    next_number
+                }
+                Entry::Vacant(entry) => {
+                    nodes.push(entry.key().clone());
+                    best.push((next_cost, Some(number)));
+                    stats.discovered += 1;
+                    *entry.insert(nodes.len() - 1)
+                }
+            };
+            let estimate = next_cost + heuristic(&nodes[next_number]);
+            frontier.push(Reverse((estimate, next_cost, next_number)));
+        }
+        stats.max_frontier = stats.max_frontier.max(frontier.len());
+    }
+    None
+}
+
+fn reconstruct<N: Eq + Hash + Clone>(parents: &HashMap<N, Option<N>>, goal: N) -> Vec<N> {
+    let mut path = vec![goal];
+    while let Some(Some(parent)) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    path
+}
@@ -0,0 +1,263 @@
+//Submitting answers to the website. All the site says about a wrong answer is whether it is too high
+//or too low, and it makes us wait longer after each one, so what it said is written down in an
+//answer book to avoid spending a guess on something that is already known to be wrong.
+
+use std::fmt::{self, Display};
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::error::Error;
+use crate::runner::{Day, Part};
+use crate::website::Website;
+
+///The answer book's name in a year's directory
+pub const ANSWER_BOOK: &str = "answer_book.txt";
+
+const HEADER: &str = "\
+# What the website said about the answers submitted to it, kept by the submit command
+#
+# One entry per line: <day> <part> <verdict> <answer>
+# - the verdict is right, wrong, too_high or too_low
+";
+
+///What the site judged an answer to be
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    ///Wrong without a hint as to which way, e.g. for an answer that isn't a number
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = match self {
+            Verdict::Right => "right",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+        };
+        write!(f, "{}", verdict)
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "right" => Ok(Verdict::Right),
+            "wrong" => Ok(Verdict::Wrong),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            _ => Err(format!("'{}' is not a verdict", s)),
+        }
+    }
+}
+
+///What the site made of a submission
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Response {
+    Judged(Verdict),
+    ///The last answer was too recent for this one to be looked at, with how long is left to wait
+    Wait(String),
+    ///The part has already been solved, or it is part b and part a hasn't been
+    WrongLevel,
+}
+
+///Somewhere that answers can be submitted to
+pub trait Submitter {
+    fn submit(&self, year: u16, day: Day, part: Part, answer: &str) -> Result<Response, Error>;
+}
+
+impl Submitter for Website {
+    fn submit(&self, year: u16, day: Day, part: Part, answer: &str) -> Result<Response, Error> {
+        let path = format!("/{}/day/{}/answer", year, day);
+        let level = match part {
+            Part::A => "1",
+            Part::B => "2",
+            Part::Both => return Err(Error::website(&self.url(&path), "a+b can't be submitted as one answer")),
+        };
+        let page = self.post_form(&path, &[("level", level), ("answer", answer)])?;
+        read_response(&page).ok_or_else(|| Error::website(&self.url(&path), "the response wasn't recognised"))
+    }
+}
+
+///Pick out what the site said from the page it responded with
+fn read_response(page: &str) -> Option<Response> {
+    if page.contains("That's the right answer") {
+        Some(Response::Judged(Verdict::Right))
+    } else if page.contains("That's not the right answer") {
+        let verdict = if page.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if page.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        };
+        Some(Response::Judged(verdict))
+    } else if page.contains("You gave an answer too recently") {
+        //"... You have 43s left to wait."
+        let left = page
+            .split_once("You have ")
+            .and_then(|(_, rest)| rest.split_once(" left to wait"))
+            .map_or("a while", |(left, _)| left);
+        Some(Response::Wait(String::from(left)))
+    } else if page.contains("You don't seem to be solving the right level") {
+        Some(Response::WrongLevel)
+    } else {
+        None
+    }
+}
+
+///An answer that the site has judged
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guess {
+    pub day: Day,
+    pub part: Part,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+///Why an answer wasn't submitted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    ///The part has already been solved, with this answer
+    AlreadySolved(String),
+    ///This exact answer has already been judged
+    AlreadyGuessed(Verdict),
+    ///The answer is at least as high as a guess that was too high
+    AboveBound(String),
+    ///The answer is at most as low as a guess that was too low
+    BelowBound(String),
+}
+
+impl Refusal {
+    ///Whether the answer may still be right, so that it can be forced through. An answer that is
+    /// out of bounds can only be right if the answer book is.
+    pub fn can_force(&self) -> bool {
+        matches!(self, Refusal::AboveBound(_) | Refusal::BelowBound(_))
+    }
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => write!(f, "already solved, the answer was {}", answer),
+            Refusal::AlreadyGuessed(verdict) => write!(f, "already submitted, it was {}", verdict),
+            Refusal::AboveBound(bound) => write!(f, "{} was already too high", bound),
+            Refusal::BelowBound(bound) => write!(f, "{} was already too low", bound),
+        }
+    }
+}
+
+///The guesses submitted so far, kept in a file that each new guess is added to
+pub struct AnswerBook {
+    path: PathBuf,
+    guesses: Vec<Guess>,
+}
+
+impl AnswerBook {
+    ///Read the book at path, there being no file is an empty book
+    pub fn load(path: &Path) -> Result<AnswerBook, Error> {
+        let name = path.display().to_string();
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(Error::io(&name, None, err)),
+        };
+        let mut guesses = Vec::new();
+        for (index, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let guess = read_guess(line).map_err(|message| Error::parse(&name, index + 1, line, message))?;
+            guesses.push(guess);
+        }
+        Ok(AnswerBook { path: path.to_path_buf(), guesses })
+    }
+
+    pub fn guesses(&self, day: Day, part: Part) -> impl Iterator<Item = &Guess> {
+        self.guesses.iter().filter(move |guess| guess.day == day && guess.part == part)
+    }
+
+    ///Whether the answer is worth submitting given what is known already
+    pub fn check(&self, day: Day, part: Part, answer: &str) -> Result<(), Refusal> {
+        if let Some(right) = self.guesses(day, part).find(|guess| guess.verdict == Verdict::Right) {
+            return Err(Refusal::AlreadySolved(right.answer.clone()));
+        }
+        if let Some(guess) = self.guesses(day, part).find(|guess| guess.answer == answer) {
+            return Err(Refusal::AlreadyGuessed(guess.verdict));
+        }
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+        let bound = |verdict: Verdict| {
+            self.guesses(day, part)
+                .filter(move |guess| guess.verdict == verdict)
+                .filter_map(|guess| guess.answer.parse::<i128>().ok())
+        };
+        if let Some(high) = bound(Verdict::TooHigh).min().filter(|high| value >= *high) {
+            return Err(Refusal::AboveBound(high.to_string()));
+        }
+        if let Some(low) = bound(Verdict::TooLow).max().filter(|low| value <= *low) {
+            return Err(Refusal::BelowBound(low.to_string()));
+        }
+        Ok(())
+    }
+
+    ///Add the guess to the book and its file, which is created if need be
+    pub fn record(&mut self, guess: Guess) -> Result<(), Error> {
+        let name = self.path.display().to_string();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|err| Error::io(&name, None, err))?;
+        let new = file.metadata().map(|metadata| metadata.len() == 0).unwrap_or(false);
+        let header = if new { HEADER } else { "" };
+        writeln!(file, "{}{} {} {} {}", header, guess.day, guess.part, guess.verdict, guess.answer)
+            .map_err(|err| Error::io(&name, None, err))?;
+        self.guesses.push(guess);
+        Ok(())
+    }
+}
+
+fn read_guess(line: &str) -> Result<Guess, String> {
+    let mut fields = line.splitn(4, ' ');
+    let mut next = |what: &str| fields.next().filter(|field| !field.is_empty()).ok_or(format!("missing the {}", what));
+    let day = next("day")?.parse().map_err(|_| String::from("the day isn't a number"))?;
+    let part = match next("part")? {
+        "a" => Part::A,
+        "b" => Part::B,
+        part => return Err(format!("'{}' is not a part", part)),
+    };
+    let verdict = next("verdict")?.parse()?;
+    let answer = String::from(next("answer")?);
+    Ok(Guess { day, part, verdict, answer })
+}
+
+///What came of trying to submit an answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Submission {
+    Sent(Response),
+    Refused(Refusal),
+}
+
+///Submit the answer unless the book says not to, recording the verdict if one is given. force
+/// sends an answer that is out of the bounds the book knows of.
+pub fn submit(book: &mut AnswerBook, submitter: &dyn Submitter, year: u16, day: Day, part: Part, answer: &str, force: bool) -> Result<Submission, Error> {
+    if let Err(refusal) = book.check(day, part, answer) {
+        if !(force && refusal.can_force()) {
+            return Ok(Submission::Refused(refusal));
+        }
+    }
+    let response = submitter.submit(year, day, part, answer)?;
+    if let Response::Judged(verdict) = response {
+        book.record(Guess { day, part, verdict, answer: String::from(answer) })?;
+    }
+    Ok(Submission::Sent(response))
+}
@@ -0,0 +1,79 @@
+//Timing of the solutions. process_file (and the other utils that take a reduction function)
+//record how long was spent reading and accumulating the input and how long the reduction took,
+//the runner collects these for each solution that it runs.
+
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+///How long a solution took to run
+#[derive(Debug, Clone, Copy)]
+pub struct Timing {
+    pub total: Duration,
+    ///Totals over every process_file call the solution made, None if it didn't make any
+    /// (e.g. it reads the input with utils::lines)
+    pub phases: Option<Phases>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Phases {
+    ///Reading, parsing and accumulating the lines
+    pub parse: Duration,
+    pub reduce: Duration,
+}
+
+thread_local! {
+    static PHASES: RefCell<Option<Phases>> = const { RefCell::new(None) };
+}
+
+///Run the function, timing it along with the phases recorded while it runs
+pub fn measure<R>(func: impl FnOnce() -> R) -> (R, Timing) {
+    PHASES.with(|phases| phases.replace(None));
+    let start = Instant::now();
+    let result = func();
+    let total = start.elapsed();
+    let phases = PHASES.with(|phases| phases.take());
+    (result, Timing { total, phases })
+}
+
+///Run the reduction, recording it and the parse phase that started at parse_start
+pub fn reduce<T, R>(parse_start: Instant, acc: T, reduction_func: impl FnOnce(T) -> R) -> R {
+    let reduce_start = Instant::now();
+    let result = reduction_func(acc);
+    let reduce = reduce_start.elapsed();
+    PHASES.with(|phases| {
+        let mut phases = phases.borrow_mut();
+        let phases = phases.get_or_insert_with(Phases::default);
+        phases.parse += reduce_start - parse_start;
+        phases.reduce += reduce;
+    });
+    result
+}
+
+///The spread of a number of timings of the same thing
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    ///None if there are no durations, for an even number the median is the mean of the middle two
+    pub fn of(durations: impl Iterator<Item = Duration>) -> Option<Stats> {
+        let mut durations: Vec<Duration> = durations.collect();
+        if durations.is_empty() {
+            return None;
+        }
+        durations.sort();
+        let middle = durations.len() / 2;
+        let median = if durations.len().is_multiple_of(2) {
+            (durations[middle - 1] + durations[middle]) / 2
+        } else {
+            durations[middle]
+        };
+        Some(Stats { min: durations[0], median, max: durations[durations.len() - 1] })
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    format!("{:.3?}", duration)
+}
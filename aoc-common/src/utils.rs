@@ -0,0 +1,482 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::fs::File;
+use std::hash::Hash;
+use std::io::{self, BufReader, Error, BufRead, Cursor, Read};
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use crate::{error, timing};
+
+/// File name that can be given instead of a real file to read from stdin
+pub const STDIN: &str = "-";
+
+/// Where a solution reads its input from
+#[derive(Debug, Clone, Copy)]
+pub enum Input<'a> {
+    /// A file, or stdin if the name is [STDIN]
+    File(&'a str),
+    /// The input itself, e.g. the example from the puzzle
+    Text(&'a str),
+}
+
+impl<'a> Input<'a> {
+    /// What to call the input in error messages
+    pub fn name(&self) -> &'a str {
+        match self {
+            Input::File(filename) => filename,
+            Input::Text(_) => "input",
+        }
+    }
+}
+
+/// Open the input for buffered reading, a file name of [STDIN] reads from stdin.
+///
+/// stdin is read in full the first time that it is opened and then replayed for
+/// each subsequent open, so that both parts of a day can run from the same input.
+pub fn open_input(input: Input) -> Result<Box<dyn BufRead>, Error> {
+    static STDIN_CONTENTS: OnceLock<Vec<u8>> = OnceLock::new();
+    let filename = match input {
+        Input::Text(text) => return Ok(Box::new(Cursor::new(text.as_bytes().to_vec()))),
+        Input::File(filename) => filename,
+    };
+    if filename != STDIN {
+        return File::open(filename).map(|file| Box::new(BufReader::new(file)) as Box<dyn BufRead>);
+    }
+    let contents = match STDIN_CONTENTS.get() {
+        Some(contents) => contents,
+        None => {
+            let mut contents = Vec::new();
+            io::stdin().lock().read_to_end(&mut contents)?;
+            STDIN_CONTENTS.get_or_init(|| contents)
+        }
+    };
+    Ok(Box::new(Cursor::new(contents.as_slice())))
+}
+
+/// Processes the input (see [open_input]) line by line
+///
+/// The time taken to read and accumulate the lines, and to reduce them, is recorded for the
+/// runner's timing summary (see [timing]).
+///
+/// The functions can be closures, so anything that they need (e.g. the number of rounds to run)
+/// can be captured rather than being fixed in constants.
+///
+/// Type Parameters relate to:
+/// - S: type that a line is parsed into
+/// - T: intermediate 'accumulator' type that an S is converted into
+/// - R: result type
+pub fn process_file<S, T, R>(input: Input,
+                             mut line_parse_func: impl FnMut(String) -> S,
+                             zero: T,
+                             mut accumulator_func: impl FnMut(T, S) -> T,
+                             reduction_func: impl FnOnce(T) -> R) -> Result<R, error::Error> {
+    let start = Instant::now();
+    fold_lines(input, zero, |acc, _, line| {
+        Ok(accumulator_func(acc, line_parse_func(line)))
+    }).map(|acc| timing::reduce(start, acc, reduction_func))
+}
+
+/// As [process_file] but for parse functions that can fail, the first line that can't be parsed
+/// stops the processing and is returned in an [error::Error::Parse] along with its line number.
+pub fn try_process_file<S, E: Display, T, R>(input: Input,
+                                             mut line_parse_func: impl FnMut(&str) -> Result<S, E>,
+                                             zero: T,
+                                             mut accumulator_func: impl FnMut(T, S) -> T,
+                                             reduction_func: impl FnOnce(T) -> R) -> Result<R, error::Error> {
+    let start = Instant::now();
+    fold_lines(input, zero, |acc, line_number, line| {
+        line_parse_func(&line)
+            .map(|parsed| accumulator_func(acc, parsed))
+            .map_err(|err| error::Error::parse(input.name(), line_number, &line, err))
+    }).map(|acc| timing::reduce(start, acc, reduction_func))
+}
+
+/// Fold over the lines of the file, the function is also given the (1 based) line number
+fn fold_lines<T, F>(input: Input, zero: T, mut func: F) -> Result<T, error::Error>
+    where F: FnMut(T, usize, String) -> Result<T, error::Error>
+{
+    let mut lines = lines(input)?;
+    let mut acc: T = zero;
+    while let Some(line) = lines.next() {
+        acc = func(acc, lines.line_number(), line?)?;
+    }
+    Ok(acc)
+}
+
+/// Iterate over the lines of the input (see [open_input]), for when a solution is easier to
+/// write with iterator adapters than with [process_file], e.g.
+///
+/// `utils::lines(input)?.map(|line| line.map(parse)).sum::<Result<_, _>>()`
+pub fn lines(input: Input) -> Result<Lines, error::Error> {
+    let reader = open_input(input).map_err(|err| error::Error::io(input.name(), None, err))?;
+    Ok(Lines {
+        filename: String::from(input.name()),
+        line_number: 0,
+        lines: reader.lines(),
+    })
+}
+
+/// The lines of a file, see [lines]. Read errors are given with the file name and line number.
+pub struct Lines {
+    filename: String,
+    line_number: usize,
+    lines: io::Lines<Box<dyn BufRead>>,
+}
+
+impl Lines {
+    /// The (1 based) number of the line last returned
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+}
+
+impl Iterator for Lines {
+    type Item = Result<String, error::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.lines.next()?;
+        self.line_number += 1;
+        Some(line.map_err(|err| error::Error::io(&self.filename, Some(self.line_number), err)))
+    }
+}
+
+/// Processes a file record by record, where the records are groups of lines separated by blank
+/// lines (see [records]), e.g. an elf's calories or a monkey's notes.
+///
+/// The record parse function can use [Record::try_parse_lines] to parse each line, or
+/// [Record::error] to report a problem with the record as a whole.
+pub fn try_process_records<S, T, R>(input: Input,
+                                    mut record_parse_func: impl FnMut(&Record) -> Result<S, error::Error>,
+                                    zero: T,
+                                    mut accumulator_func: impl FnMut(T, S) -> T,
+                                    reduction_func: impl FnOnce(T) -> R) -> Result<R, error::Error> {
+    let start = Instant::now();
+    let mut acc: T = zero;
+    for record in records(input)? {
+        acc = accumulator_func(acc, record_parse_func(&record?)?);
+    }
+    Ok(timing::reduce(start, acc, reduction_func))
+}
+
+/// Read the N blank line separated sections of a file, for inputs made up of different parts
+/// that each need their own parsing (e.g. a drawing followed by a list of moves)
+pub fn sections<const N: usize>(input: Input) -> Result<[Record; N], error::Error> {
+    let sections: Vec<Record> = records(input)?.collect::<Result<_, _>>()?;
+    let num_sections = sections.len();
+    sections.try_into().map_err(|_| {
+        error::Error::format(input.name(), format!("expected {} sections separated by blank lines, found {}", N, num_sections))
+    })
+}
+
+/// Iterate over the records of a file, a record is a group of lines separated from the next by one
+/// or more blank lines
+pub fn records(input: Input) -> Result<Records, error::Error> {
+    lines(input).map(|lines| Records { lines })
+}
+
+/// The records of a file, see [records]
+pub struct Records {
+    lines: Lines,
+}
+
+impl Iterator for Records {
+    type Item = Result<Record, error::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut record: Option<Record> = None;
+        while let Some(line) = self.lines.next() {
+            let line = match line {
+                Ok(line) => line,
+                Err(err) => return Some(Err(err)),
+            };
+            if line.trim().is_empty() {
+                if record.is_some() {
+                    break;
+                }
+                continue;
+            }
+            record.get_or_insert_with(|| Record {
+                filename: self.lines.filename.clone(),
+                first_line_number: self.lines.line_number(),
+                lines: Vec::new(),
+            }).lines.push(line);
+        }
+        record.map(Ok)
+    }
+}
+
+/// A group of (non blank) lines from a file, see [records]
+pub struct Record {
+    filename: String,
+    first_line_number: usize,
+    lines: Vec<String>,
+}
+
+impl Record {
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Parse every line of the record, an error gives the line that couldn't be parsed
+    pub fn try_parse_lines<S, E: Display>(&self, mut line_parse_func: impl FnMut(&str) -> Result<S, E>) -> Result<Vec<S>, error::Error> {
+        self.lines.iter().enumerate()
+            .map(|(index, line)| {
+                line_parse_func(line)
+                    .map_err(|err| error::Error::parse(&self.filename, self.first_line_number + index, line, err))
+            })
+            .collect()
+    }
+
+    /// An error for a record that can't be parsed as a whole, this points to the first line
+    pub fn error(&self, message: impl Display) -> error::Error {
+        error::Error::parse(&self.filename, self.first_line_number, &self.lines[0], message)
+    }
+}
+
+///Delimiters used by [Scanner::new], the same that the old parse_next_string stopped at
+pub const DEFAULT_DELIMITERS: &[char] = &[',', ':', ' ', ';'];
+
+///Why a [Scanner] couldn't read what was asked for, column is where it was looking (from 1)
+#[derive(Debug, PartialEq, Eq)]
+pub enum ScanError {
+    EndOfLine { column: usize, expected: String },
+    Unexpected { column: usize, expected: String, found: String },
+}
+
+impl Display for ScanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScanError::EndOfLine { column, expected } =>
+                write!(f, "expected {} at column {}, found the end of the line", expected, column),
+            ScanError::Unexpected { column, expected, found } =>
+                write!(f, "expected {} at column {}, found '{}'", expected, column, found),
+        }
+    }
+}
+
+impl std::error::Error for ScanError {}
+
+///Reads a line a token at a time, tokens are separated by one or more of the delimiters.
+///
+///e.g. with '=' added to the delimiters
+/// "Valve AA has flow rate=0; tunnels lead to valves DD, II" can be read with
+/// `expect_literal("Valve")`, `next_token()`, `expect_literal("has flow rate")`, `next_int()`...
+#[derive(Debug, Clone)]
+pub struct Scanner<'a> {
+    line: &'a str,
+    position: usize,
+    delimiters: Vec<char>,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(line: &'a str) -> Scanner<'a> {
+        Scanner::with_delimiters(line, DEFAULT_DELIMITERS)
+    }
+
+    pub fn with_delimiters(line: &'a str, delimiters: &[char]) -> Scanner<'a> {
+        Scanner { line, position: 0, delimiters: delimiters.to_vec() }
+    }
+
+    ///Where the scanner is up to, this can be given back to [Scanner::rewind]
+    pub fn mark(&self) -> usize {
+        self.position
+    }
+
+    ///Go back (or forward) to a position previously returned by [Scanner::mark]
+    pub fn rewind(&mut self, position: usize) {
+        self.position = position;
+    }
+
+    ///What hasn't been read yet, including any leading delimiters
+    pub fn rest(&self) -> &'a str {
+        &self.line[self.position..]
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.peek().is_none()
+    }
+
+    ///The next token without consuming it
+    pub fn peek(&self) -> Option<&'a str> {
+        self.find_token().map(|(start, end)| &self.line[start..end])
+    }
+
+    pub fn next_token(&mut self) -> Result<&'a str, ScanError> {
+        self.next().ok_or_else(|| self.end_of_line("a token"))
+    }
+
+    ///Read the next token as a number
+    pub fn next_int<T: FromStr>(&mut self) -> Result<T, ScanError> {
+        let start = self.mark();
+        let token = self.next_token().map_err(|_| self.end_of_line("a number"))?;
+        token.parse().map_err(|_| {
+            self.rewind(start);
+            self.unexpected("a number", token)
+        })
+    }
+
+    ///Consume the words of the literal, they must be the next tokens. Delimiters in the literal only
+    /// separate the words so "ore. Each clay robot" matches "ore.  Each clay  robot" (or
+    /// "ore Each clay robot" if '.' is a delimiter). Nothing is consumed if it doesn't match.
+    pub fn expect_literal(&mut self, literal: &str) -> Result<(), ScanError> {
+        let start = self.mark();
+        let delimiters = self.delimiters.clone();
+        for word in literal.split(|c| delimiters.contains(&c)).filter(|word| !word.is_empty()) {
+            let expected = format!("'{}'", word);
+            let result = match self.peek() {
+                Some(token) if token == word => Ok(()),
+                Some(token) => Err(self.unexpected(&expected, token)),
+                None => Err(self.end_of_line(&expected)),
+            };
+            if let Err(error) = result {
+                self.rewind(start);
+                return Err(error);
+            }
+            self.next();
+        }
+        Ok(())
+    }
+
+    fn is_delimiter(&self, c: char) -> bool {
+        self.delimiters.contains(&c)
+    }
+
+    ///The byte range of the next token in the line
+    fn find_token(&self) -> Option<(usize, usize)> {
+        let rest = self.rest();
+        let start = self.position + rest.find(|c| !self.is_delimiter(c))?;
+        let end = self.line[start..].find(|c| self.is_delimiter(c)).map_or(self.line.len(), |end| start + end);
+        Some((start, end))
+    }
+
+    fn next_column(&self) -> usize {
+        self.find_token().map_or(self.line.len(), |(start, _)| start) + 1
+    }
+
+    fn end_of_line(&self, expected: &str) -> ScanError {
+        ScanError::EndOfLine { column: self.line.len() + 1, expected: String::from(expected) }
+    }
+
+    fn unexpected(&self, expected: &str, found: &str) -> ScanError {
+        ScanError::Unexpected { column: self.next_column(), expected: String::from(expected), found: String::from(found) }
+    }
+}
+
+impl<'a> Iterator for Scanner<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let (start, end) = self.find_token()?;
+        self.position = end;
+        Some(&self.line[start..end])
+    }
+}
+
+///Where a sequence starts repeating, the item at start + length is the same as the one at start
+/// and it goes round again from there, see [find_cycle]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    ///The index of the first item that is the same as the one at index, either before the cycle or
+    /// in its first time round
+    pub fn first_seen(&self, index: usize) -> usize {
+        if index < self.start {
+            index
+        } else {
+            self.start + (index - self.start) % self.length
+        }
+    }
+
+    ///How many times round the cycle it takes to get from [Cycle::first_seen] to index
+    pub fn times_round(&self, index: usize) -> usize {
+        index.saturating_sub(self.start) / self.length
+    }
+}
+
+///Look for the first key that is the same as an earlier one, for a simulation that gives the key
+/// of each state it goes through. The key has to be everything that decides what the next state
+/// is, then once a key comes round again so will all the ones that followed it.
+///
+///None if the keys run out without repeating.
+pub fn find_cycle<K: Hash + Eq>(keys: impl IntoIterator<Item = K>) -> Option<Cycle> {
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    for (index, key) in keys.into_iter().enumerate() {
+        match seen.entry(key) {
+            Entry::Occupied(entry) => {
+                let start = *entry.get();
+                return Some(Cycle { start, length: index - start });
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(index);
+            }
+        }
+    }
+    None
+}
+
+///Pull every integer out of the string, ignoring whatever is around them. A '-' directly before
+/// the digits makes the number negative (if T can be).
+pub fn extract_all_ints<T: FromStr>(s: &str) -> Result<Vec<T>, T::Err> {
+    let mut numbers = Vec::new();
+    let mut start = None;
+    for (index, c) in s.char_indices().chain([(s.len(), ' ')]) {
+        match (start, c) {
+            (None, '-' | '0'..='9') => start = Some(index),
+            (Some(_), '0'..='9') => {}
+            (Some(from), _) => {
+                let number = &s[from..index];
+                if number != "-" {
+                    numbers.push(number.parse()?);
+                }
+                start = if c == '-' { Some(index) } else { None };
+            }
+            (None, _) => {}
+        }
+    }
+    Ok(numbers)
+}
+
+struct WriteAdapter<W>(W);
+
+impl<W> fmt::Write for WriteAdapter<W>
+where
+    W: io::Write,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write_all(s.as_bytes()).map_err(|_| fmt::Error)
+    }
+    fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> fmt::Result {
+        self.0.write_fmt(args).map_err(|_| fmt::Error)
+    }
+}
+
+
+pub fn output_into_iter<W: fmt::Write, I>(f: &mut W, separator: &str, iter: &mut I)
+where
+    I: Iterator,
+    I::Item: Display,
+{
+    if let Some(thing) = iter.next() {
+        write!(f, "{}", thing).unwrap();
+    }
+    let mut next_thing = iter.next();
+    while next_thing.is_some() {
+        write!(f, "{}{}", separator, next_thing.unwrap()).unwrap();
+        next_thing = iter.next();
+    }
+}
+
+pub fn output_into_iter_io<W: io::Write, I>(f: W, separator: &str, iter: &mut I)
+where
+    I: Iterator,
+    I::Item: Display,
+{
+    output_into_iter(&mut WriteAdapter(f), separator, iter)
+}
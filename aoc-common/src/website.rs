@@ -0,0 +1,98 @@
+//Talking to the Advent of Code website, which knows who we are from the session cookie that the
+//browser is given on logging in.
+
+use std::env;
+use std::time::Duration;
+
+use ureq::Agent;
+
+use crate::error::Error;
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+///Environment variable holding the session cookie's value
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+///Environment variable holding the url of the repository these solutions are published at
+pub const REPOSITORY_ENV: &str = "AOC_REPOSITORY";
+
+///Environment variable holding how to get in touch with whoever is running them, e.g. an email address
+pub const CONTACT_ENV: &str = "AOC_CONTACT";
+
+///The site asks that automated requests say where they come from and who to contact about them
+pub fn user_agent(repository: &str, contact: &str) -> String {
+    format!("{} by {}", repository.trim(), contact.trim())
+}
+
+pub struct Website {
+    base_url: String,
+    session: String,
+    user_agent: String,
+    agent: Agent,
+}
+
+impl Website {
+    pub fn new(session: &str, user_agent: &str) -> Website {
+        Website::with_base_url(BASE_URL, session, user_agent)
+    }
+
+    ///For talking to something other than the real site, e.g. a stand in for testing
+    pub fn with_base_url(base_url: &str, session: &str, user_agent: &str) -> Website {
+        let agent = Agent::config_builder()
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .into();
+        Website {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            user_agent: user_agent.to_string(),
+            agent,
+        }
+    }
+
+    ///The site with the session from [SESSION_ENV], None if it isn't set. The user agent is made
+    /// from [REPOSITORY_ENV] and [CONTACT_ENV], which have to be set along with the session.
+    pub fn from_env() -> Result<Option<Website>, String> {
+        let Some(session) = env_var(SESSION_ENV) else {
+            return Ok(None);
+        };
+        let repository = env_var(REPOSITORY_ENV)
+            .ok_or(format!("Set {} to the url of the repository the requests come from", REPOSITORY_ENV))?;
+        let contact = env_var(CONTACT_ENV)
+            .ok_or(format!("Set {} to how the site can get in touch about the requests, e.g. an email address", CONTACT_ENV))?;
+        Ok(Some(Website::new(&session, &user_agent(&repository, &contact))))
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    ///The body of the page at path, e.g. /2022/day/1/input
+    pub fn get(&self, path: &str) -> Result<String, Error> {
+        let url = self.url(path);
+        self.agent
+            .get(&url)
+            .header("Cookie", &format!("session={}", self.session))
+            .header("User-Agent", &self.user_agent)
+            .call()
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .map_err(|err| Error::website(&url, err))
+    }
+
+    ///Post a form to path, as the answer form on a day's page does, and return the page that comes back
+    pub fn post_form(&self, path: &str, form: &[(&str, &str)]) -> Result<String, Error> {
+        let url = self.url(path);
+        self.agent
+            .post(&url)
+            .header("Cookie", &format!("session={}", self.session))
+            .header("User-Agent", &self.user_agent)
+            .send_form(form.iter().copied())
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .map_err(|err| Error::website(&url, err))
+    }
+}
+
+///The variable's value, None if it isn't set or is blank
+fn env_var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.trim().is_empty())
+}
@@ -0,0 +1,90 @@
+use aoc_common::grid::{self, Bounds, DenseGrid, Grid, Pos, SparseGrid, Wrap};
+
+const MAP: &str = "\
+#.#
+..#
+#..
+";
+
+fn map() -> DenseGrid<char> {
+    DenseGrid::parse(MAP, |_, c| Ok::<char, String>(c)).unwrap()
+}
+
+#[test]
+fn dense_grids_parse_and_display() {
+    let grid = map();
+    assert_eq!((grid.width(), grid.height()), (3, 3));
+    assert_eq!(grid.bounds(), Bounds::new(0, 0, 2, 2));
+    assert_eq!(grid[Pos::new(2, 1)], '#');
+    assert_eq!(grid.get(Pos::new(3, 1)), None);
+    assert_eq!(grid.get(Pos::new(-1, 1)), None);
+    assert_eq!(grid.row(2), &['#', '.', '.']);
+    assert_eq!(grid.iter().filter(|(_, c)| **c == '#').count(), 4);
+    assert_eq!(grid.to_string(), MAP);
+
+    let ragged = DenseGrid::parse("..\n...\n", |_, c| Ok::<char, String>(c));
+    assert_eq!(ragged.unwrap_err(), "line 2: the row is 3 long where the others are 2");
+    let bad = DenseGrid::parse("1x", |_, c| c.to_digit(10).ok_or(format!("'{}' isn't a digit", c)));
+    assert_eq!(bad.unwrap_err(), "'x' isn't a digit");
+
+    //A line at a time, as the rows are read from a file
+    let mut parse_row = grid::row_parser(|c| c.to_digit(10).ok_or(format!("'{}' isn't a digit", c)));
+    assert_eq!(parse_row("12"), Ok(vec![1, 2]));
+    assert_eq!(parse_row("3x"), Err(String::from("'x' isn't a digit")));
+    assert_eq!(parse_row("345"), Err(String::from("the row is 3 long where the first is 2")));
+
+    let mut grid = DenseGrid::new();
+    assert_eq!(grid.try_push_row(vec![1, 2]), Ok(()));
+    assert_eq!(grid.try_push_row(vec![3]), Err(String::from("the row is 1 long where the others are 2")));
+    assert_eq!((grid.width(), grid.height()), (2, 1));
+}
+
+#[test]
+fn neighbours_stay_in_bounds_or_wrap() {
+    let grid = map();
+    let corner = Pos::new(0, 0);
+    let unwrapped: Vec<Pos> = grid.neighbours4(corner, Wrap::None).collect();
+    assert_eq!(unwrapped, vec![Pos::new(1, 0), Pos::new(0, 1)]);
+    let wrapped: Vec<Pos> = grid.neighbours4(corner, Wrap::Around).collect();
+    assert_eq!(wrapped, vec![Pos::new(0, 2), Pos::new(1, 0), Pos::new(0, 1), Pos::new(2, 0)]);
+    assert_eq!(grid.neighbours8(corner, Wrap::None).count(), 3);
+    assert_eq!(grid.neighbours8(Pos::new(1, 1), Wrap::None).count(), 8);
+    assert_eq!(grid.neighbours8(corner, Wrap::Around).filter(|pos| grid[*pos] == '#').count(), 3);
+
+    let bounds = Bounds::new(1, 1, 3, 4);
+    assert_eq!(bounds.wrap(Pos::new(0, 5)), Pos::new(3, 1));
+    assert_eq!(bounds.wrap(Pos::new(-5, -1)), Pos::new(1, 3));
+    assert_eq!(bounds.area(), 12);
+}
+
+#[test]
+fn sparse_grids_keep_their_bounds() {
+    let mut grid = SparseGrid::parse(MAP, |_, c| Ok::<Option<char>, String>((c == '#').then_some('#'))).unwrap();
+    assert_eq!(grid.len(), 4);
+    assert_eq!(grid.bounds(), Bounds::new(0, 0, 2, 2));
+    assert_eq!(grid.to_string(), MAP);
+
+    grid.insert(Pos::new(-2, 1), 'o');
+    assert_eq!(grid.bounds(), Bounds::new(-2, 0, 2, 2));
+    assert_eq!(grid.neighbours4(Pos::new(-2, 1), Wrap::None).count(), 3);
+    grid.remove(Pos::new(-2, 1));
+    assert_eq!(grid.bounds(), Bounds::new(0, 0, 2, 2));
+    grid.remove(Pos::new(0, 2));
+    assert_eq!(grid.bounds(), Bounds::new(0, 0, 2, 1));
+    grid.remove(Pos::new(0, 0));
+    assert_eq!(grid.bounds(), Bounds::new(2, 0, 2, 1));
+    assert_eq!(grid.to_string(), "#\n#\n");
+
+    let empty: SparseGrid<char> = SparseGrid::new();
+    assert!(empty.bounds().is_empty());
+    assert_eq!(empty.locate(Pos::new(0, 0), Wrap::Around), None);
+}
+
+#[test]
+fn bounds_render_each_position() {
+    let path = [Pos::new(0, 1), Pos::new(1, 1), Pos::new(1, 2)];
+    let grid = map();
+    let drawn = grid.bounds().render(|pos| if path.contains(&pos) { 'o' } else { grid[pos] });
+    assert_eq!(drawn.to_string(), "#.#\noo#\n#o.\n");
+    assert_eq!(Bounds::of(path).to_string(), "(0, 1) -> (1, 2)");
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+
+use aoc_common::inputs::InputProvider;
+use aoc_common::website::Website;
+
+use common::{MockServer, AGENT};
+
+fn cache_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("inputs-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+#[test]
+fn fetches_into_the_cache_then_uses_it() {
+    let server = MockServer::start(vec![(200, "1000\n2000\n")]);
+    let cache = cache_directory("fetch");
+    let provider = InputProvider::new(2022, &cache).with_fetcher(Website::with_base_url(&server.url, "abc123", AGENT));
+
+    let path = provider.input(3).unwrap();
+    assert_eq!(path, cache.join("3/input.txt"));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+    assert_eq!(provider.input(3).unwrap(), path);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1, "the second input should come from the cache");
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].path, "/2022/day/3/input");
+    assert_eq!(requests[0].header("Cookie"), Some("session=abc123"));
+    assert_eq!(requests[0].header("User-Agent"), Some(AGENT));
+    fs::remove_dir_all(cache).unwrap();
+}
+
+#[test]
+fn cache_only_without_a_fetcher() {
+    let cache = cache_directory("cache-only");
+    let provider = InputProvider::new(2022, &cache);
+    fs::create_dir_all(cache.join("1")).unwrap();
+    fs::write(cache.join("1/input.txt"), "1\n").unwrap();
+    //As new-day leaves it
+    fs::create_dir_all(cache.join("2")).unwrap();
+    fs::write(cache.join("2/input.txt"), "").unwrap();
+
+    assert_eq!(provider.input(1).unwrap(), cache.join("1/input.txt"));
+    let error = provider.input(2).unwrap_err().to_string();
+    assert!(error.ends_with("not cached, and there is no session to fetch it with"), "{}", error);
+    assert!(provider.input(4).is_err());
+    fs::remove_dir_all(cache).unwrap();
+}
+
+#[test]
+fn failed_fetches_are_not_cached() {
+    let server = MockServer::start(vec![(400, "Puzzle inputs differ by user.  Please log in to get your puzzle input.")]);
+    let cache = cache_directory("failed");
+    let provider = InputProvider::new(2022, &cache).with_fetcher(Website::with_base_url(&server.url, "expired", AGENT));
+
+    let error = provider.input(5).unwrap_err().to_string();
+    assert!(error.starts_with(&format!("{}/2022/day/5/input: ", server.url)), "{}", error);
+    assert!(error.contains("400"), "{}", error);
+    assert!(!cache.join("5/input.txt").exists());
+    fs::remove_dir_all(cache).unwrap();
+}
@@ -0,0 +1,107 @@
+use std::cmp::Ordering;
+
+use aoc_common::optimise::{self, Problem};
+
+///Packing a knapsack with the most valuable of some items that won't all fit
+struct Knapsack {
+    capacity: u32,
+    ///(weight, value), by value for their weight so that the bound can take the best first
+    items: Vec<(u32, u32)>,
+}
+
+///Which of the items have been looked at so far, and what has been packed
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Packing {
+    next_item: usize,
+    weight: u32,
+    value: u32,
+    packed: Vec<usize>,
+}
+
+impl Problem for Knapsack {
+    type State = Packing;
+    type Score = u32;
+
+    fn successors(&self, packing: &Packing) -> Vec<Packing> {
+        let Some(&(weight, value)) = self.items.get(packing.next_item) else {
+            return Vec::new();
+        };
+        let leave = Packing { next_item: packing.next_item + 1, ..packing.clone() };
+        if packing.weight + weight > self.capacity {
+            return vec![leave];
+        }
+        let mut take = Packing {
+            weight: packing.weight + weight,
+            value: packing.value + value,
+            ..leave.clone()
+        };
+        take.packed.push(packing.next_item);
+        vec![take, leave]
+    }
+
+    fn score(&self, packing: &Packing) -> u32 {
+        packing.value
+    }
+
+    ///As if items could be cut to fill the knapsack
+    fn bound(&self, packing: &Packing) -> u32 {
+        let mut space = self.capacity - packing.weight;
+        let mut bound = packing.value;
+        for &(weight, value) in &self.items[packing.next_item..] {
+            let taken = weight.min(space);
+            bound += value * taken / weight;
+            space -= taken;
+        }
+        bound
+    }
+
+    ///Whatever has packed the most value so far
+    fn compare(&self, a: &Packing, b: &Packing) -> Ordering {
+        b.value.cmp(&a.value)
+    }
+}
+
+fn knapsack() -> Knapsack {
+    //Greedily packing the 10 first leaves no room for the two 7s
+    Knapsack {
+        capacity: 10,
+        items: vec![(6, 10), (5, 7), (5, 7), (1, 1)],
+    }
+}
+
+fn start() -> Packing {
+    Packing { next_item: 0, weight: 0, value: 0, packed: Vec::new() }
+}
+
+#[test]
+fn branch_and_bound_finds_the_best() {
+    let best = optimise::branch_and_bound(&knapsack(), start());
+    assert_eq!(best.score, 14);
+    assert_eq!(best.state.packed, vec![1, 2]);
+    assert!(best.proven_optimal);
+    assert!(best.stats.pruned > 0);
+}
+
+#[test]
+fn a_narrow_beam_can_miss_the_best() {
+    let greedy = optimise::beam_search(&knapsack(), start(), 1);
+    assert_eq!(greedy.score, 11);
+    assert_eq!(greedy.state.packed, vec![0, 3]);
+    assert!(!greedy.proven_optimal);
+    assert!(greedy.stats.dropped > 0);
+
+    //With room for everything nothing is dropped, so the beam knows it has the best
+    let wide = optimise::beam_search(&knapsack(), start(), 16);
+    assert_eq!(wide.score, 14);
+    assert!(wide.proven_optimal);
+    assert_eq!(wide.stats.dropped, 0);
+}
+
+#[test]
+fn a_start_with_no_choices_is_the_best() {
+    let empty = Knapsack { capacity: 3, items: Vec::new() };
+    let best = optimise::branch_and_bound(&empty, start());
+    assert_eq!((best.score, best.state), (0, start()));
+    assert!(best.proven_optimal);
+    assert_eq!(best.stats.expanded, 0);
+}
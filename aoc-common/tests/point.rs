@@ -0,0 +1,55 @@
+use aoc_common::direction::{Direction4, Direction8};
+use aoc_common::point::{Point2, Point3};
+
+#[test]
+fn points_are_vectors() {
+    let a = Point2::new(3, -2);
+    let b = Point2::new(-1, 4);
+    assert_eq!(a + b, Point2::new(2, 2));
+    assert_eq!(a - b, Point2::new(4, -6));
+    assert_eq!(a * 3, Point2::new(9, -6));
+    assert_eq!(-a, Point2::new(-3, 2));
+    assert_eq!(a.manhattan(b), 10);
+    assert_eq!((a - b).signum(), Point2::new(1, -1));
+    let mut c = a;
+    c += b;
+    c -= Point2::new(2, 2);
+    assert_eq!(c, Point2::ORIGIN);
+    assert_eq!(a.to_string(), "(3, -2)");
+
+    let d = Point3::new(1i64, 2, 3);
+    assert_eq!(d + d * 2 - Point3::new(3, 6, 9), Point3::ORIGIN);
+    assert_eq!(d.manhattan(Point3::new(-1, 2, 5)), 4);
+    assert_eq!(d.neighbours6().iter().filter(|n| n.manhattan(d) == 1).count(), 6);
+}
+
+#[test]
+fn rotations_turn_about_the_origin() {
+    let east: Point2<i32> = Direction4::East.offset();
+    assert_eq!(east.rotate_left(), Direction4::North.offset());
+    assert_eq!(east.rotate_right(), Direction4::South.offset());
+    let p = Point2::new(2, 5);
+    assert_eq!(p.rotate_left().rotate_right(), p);
+    assert_eq!(p.rotate_right().rotate_right(), -p);
+}
+
+#[test]
+fn directions_turn() {
+    for direction in Direction4::ALL {
+        assert_eq!(direction.turn_left().turn_right(), direction);
+        assert_eq!(direction.turn_right().turn_right(), direction.reverse());
+        //Turning the direction turns its offset
+        assert_eq!(direction.turn_left().offset::<i8>(), direction.offset::<i8>().rotate_left());
+        assert_eq!(Direction8::from(direction).offset::<i8>(), direction.offset());
+    }
+    assert_eq!(Direction4::West.turn_right(), Direction4::North);
+    assert_eq!(Direction8::NorthWest.turn_right(), Direction8::North);
+    assert_eq!(Direction8::North.turn_left(), Direction8::NorthWest);
+    assert_eq!(Direction8::SouthEast.reverse(), Direction8::NorthWest);
+    assert_eq!(Direction8::ALL.iter().filter(|direction| direction.is_diagonal()).count(), 4);
+    assert_eq!(Direction4::ALL.map(|direction| direction.to_string()).concat(), "^>v<");
+
+    let p = Point2::new(0i64, 0);
+    assert_eq!(p.neighbours4(), Direction4::ALL.map(|direction| p.step(direction)));
+    assert_eq!(p.neighbours8()[1], Point2::new(1, -1));
+}
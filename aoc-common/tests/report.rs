@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use aoc_common::error::Error;
+use aoc_common::report::{self, Format};
+use aoc_common::runner::{Answer, Outcome, Part, Run, Solution, Solver};
+use aoc_common::timing::{Phases, Timing};
+
+const SOLUTION_A: Solution = Solution { day: 17, part: Part::A, name: "seventeen::_17a", solver: Solver::NotAvailable("") };
+const SOLUTION_B: Solution = Solution { day: 17, part: Part::B, name: "seventeen::_17b", solver: Solver::NotAvailable("") };
+
+///Part b benchmarked over two runs, and part a failing with an error that needs quoting in csv
+fn runs() -> Vec<Run<'static>> {
+    let timing = |total: u64| Timing {
+        total: Duration::from_secs(total),
+        phases: Some(Phases { parse: Duration::from_millis(500), reduce: Duration::from_millis(total * 1000 - 500) }),
+    };
+    let error = Error::parse("17/input.txt", 3, "<>x", "unexpected 'x', expected \"<\" or \">\"");
+    vec![
+        Run {
+            solution: &SOLUTION_B,
+            input: String::from("17/input.txt"),
+            outcome: Outcome::Solved { answer: Answer::new(1514285714288i64), timings: vec![timing(3), timing(1)] },
+        },
+        Run { solution: &SOLUTION_A, input: String::from("17/input.txt"), outcome: Outcome::Failed(error) },
+    ]
+}
+
+fn write(format: Format) -> String {
+    let mut written = Vec::new();
+    report::write(&mut written, format, &runs()).unwrap();
+    String::from_utf8(written).unwrap()
+}
+
+#[test]
+fn json_has_a_record_per_run() {
+    let records: serde_json::Value = serde_json::from_str(&write(Format::Json)).unwrap();
+    let solved = &records[0];
+    assert_eq!((solved["day"].as_u64(), solved["part"].as_str(), solved["status"].as_str()), (Some(17), Some("b"), Some("solved")));
+    assert_eq!(solved["input"], "17/input.txt");
+    assert_eq!(solved["answer"], "1514285714288");
+    assert_eq!(solved["answer_type"], "i64");
+    assert!(solved["error"].is_null());
+    assert_eq!(solved["runs"], 2);
+    assert_eq!((solved["total_min"].as_f64(), solved["total_median"].as_f64(), solved["total_max"].as_f64()), (Some(1.0), Some(2.0), Some(3.0)));
+    assert_eq!((solved["parse_median"].as_f64(), solved["reduce_median"].as_f64()), (Some(0.5), Some(1.5)));
+
+    let failed = &records[1];
+    assert_eq!((failed["part"].as_str(), failed["status"].as_str()), (Some("a"), Some("failed")));
+    assert_eq!(failed["error"], "17/input.txt:3: unexpected 'x', expected \"<\" or \">\": '<>x'");
+    assert!(failed["answer"].is_null() && failed["answer_type"].is_null() && failed["total_median"].is_null());
+    assert_eq!(failed["runs"], 0);
+    assert_eq!(records.as_array().map(Vec::len), Some(2));
+}
+
+#[test]
+fn csv_has_a_header_and_a_line_per_run() {
+    let written = write(Format::Csv);
+    let lines: Vec<&str> = written.lines().collect();
+    assert_eq!(lines, [
+        "day,part,input,status,answer,answer_type,error,runs,total_min,total_median,total_max,parse_median,reduce_median",
+        "17,b,17/input.txt,solved,1514285714288,i64,,2,1.0,2.0,3.0,0.5,1.5",
+        //The error has commas and quotes in it, so it is quoted with the quotes doubled
+        "17,a,17/input.txt,failed,,,\"17/input.txt:3: unexpected 'x', expected \"\"<\"\" or \"\">\"\": '<>x'\",0,,,,,",
+    ]);
+}
+
+#[test]
+fn text_isnt_a_report_format() {
+    assert!(report::write(Vec::new(), Format::Text, &runs()).is_err());
+}
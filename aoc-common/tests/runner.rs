@@ -0,0 +1,17 @@
+use aoc_common::runner::Days;
+
+#[test]
+fn days_run_from_1_to_25() {
+    let days = |s: &str| s.parse::<Days>();
+    assert!(days("17").unwrap().contains(17));
+    assert!(days("1..26").unwrap().contains(25));
+    assert!(!days("1..10").unwrap().contains(10));
+    assert!(days("1..=10").unwrap().contains(10));
+
+    assert_eq!(days("30").unwrap_err(), "'30' goes outside the days, they run from 1 to 25");
+    assert_eq!(days("0..40").unwrap_err(), "'0..40' goes outside the days, they run from 1 to 25");
+    assert!(days("0").is_err());
+    assert!(days("25..=26").is_err());
+    assert_eq!(days("5..5").unwrap_err(), "'5..5' is an empty range");
+    assert_eq!(days("x").unwrap_err(), "'x' is not a day number");
+}
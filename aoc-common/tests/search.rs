@@ -0,0 +1,67 @@
+use aoc_common::grid::{DenseGrid, Grid, Pos, Wrap};
+use aoc_common::search;
+
+const MAZE: &str = "\
+S.#.....
+.##.###.
+....#...
+.#.##.#.
+.#..9...
+......#E
+";
+
+fn maze() -> DenseGrid<char> {
+    DenseGrid::parse(MAZE, |_, c| Ok::<char, String>(c)).unwrap()
+}
+
+fn open(maze: &DenseGrid<char>, pos: Pos) -> Vec<Pos> {
+    maze.neighbours4(pos, Wrap::None).filter(|next| maze[*next] != '#').collect()
+}
+
+const START: Pos = Pos::new(0, 0);
+const END: Pos = Pos::new(7, 5);
+
+#[test]
+fn bfs_finds_the_fewest_steps() {
+    let maze = maze();
+    let path = search::bfs(START, |pos| open(&maze, *pos), |pos| *pos == END).unwrap();
+    assert_eq!(path.cost, 12);
+    assert_eq!(path.nodes.len(), 13);
+    assert_eq!(path.nodes[0], START);
+    assert_eq!(*path.goal(), END);
+    assert!(path.nodes.windows(2).all(|step| step[0].manhattan(step[1]) == 1));
+    assert!(path.stats.expanded > 0 && path.stats.discovered >= path.stats.expanded);
+
+    assert!(search::bfs(START, |pos| open(&maze, *pos), |pos| *pos == Pos::new(2, 0)).is_none());
+    let steps = search::reachable(START, |pos| open(&maze, *pos));
+    assert_eq!(steps[&END], 12);
+    assert_eq!(steps.len(), MAZE.chars().filter(|c| !matches!(c, '#' | '\n')).count());
+}
+
+#[test]
+fn dijkstra_and_astar_take_the_cheapest_path() {
+    let maze = maze();
+    //Stepping onto a digit costs that much, which makes the route past the 9 dearer than going round
+    let weighted = |pos: &Pos| {
+        open(&maze, *pos)
+            .into_iter()
+            .map(|next| (next, maze[next].to_digit(10).map_or(1, i64::from)))
+            .collect::<Vec<_>>()
+    };
+    let cheapest = search::dijkstra(START, weighted, |pos| *pos == END).unwrap();
+    assert_eq!(cheapest.cost, 14);
+    assert!(!cheapest.nodes.contains(&Pos::new(4, 4)));
+
+    let guided = search::astar(START, weighted, |pos| pos.manhattan(END), |pos| *pos == END).unwrap();
+    assert_eq!(guided.cost, cheapest.cost);
+    assert_eq!(guided.nodes.len(), cheapest.nodes.len());
+    assert!(guided.stats.expanded <= cheapest.stats.expanded);
+}
+
+#[test]
+fn the_start_can_be_the_goal() {
+    let path = search::dijkstra(5u32, |n| [(n + 1, 1u32)], |n| *n == 5).unwrap();
+    assert_eq!((path.cost, path.nodes), (0, vec![5]));
+    let counting = search::bfs(1u32, |n| [n * 2, n + 1], |n| *n == 10).unwrap();
+    assert_eq!(counting.nodes, vec![1, 2, 4, 5, 10]);
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+
+use aoc_common::runner::Part;
+use aoc_common::submit::{self, AnswerBook, Guess, Refusal, Response, Submission, Submitter, Verdict};
+use aoc_common::website::Website;
+
+use common::{MockServer, AGENT};
+
+const RIGHT: &str = "<main><article><p>That's the right answer!  You are one gold star closer.</p></article></main>";
+const TOO_HIGH: &str = "<main><article><p>That's not the right answer; your answer is too high.  Please wait one minute before trying again.</p></article></main>";
+const TOO_LOW: &str = "<main><article><p>That's not the right answer; your answer is too low.</p></article></main>";
+const TOO_SOON: &str = "<main><article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 43s left to wait.</p></article></main>";
+
+fn book_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("answer-book-{}-{}.txt", name, std::process::id()));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn posts_the_answer_and_reads_the_verdict() {
+    let server = MockServer::start(vec![(200, TOO_HIGH), (200, TOO_SOON), (200, RIGHT)]);
+    let website = Website::with_base_url(&server.url, "abc123", AGENT);
+
+    assert_eq!(website.submit(2022, 17, Part::B, "1514285714300").unwrap(), Response::Judged(Verdict::TooHigh));
+    assert_eq!(website.submit(2022, 17, Part::B, "1514285714288").unwrap(), Response::Wait(String::from("43s")));
+    assert_eq!(website.submit(2022, 17, Part::A, "3068").unwrap(), Response::Judged(Verdict::Right));
+
+    let requests = server.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/2022/day/17/answer");
+    assert_eq!(requests[0].header("Cookie"), Some("session=abc123"));
+    assert_eq!(requests[0].header("User-Agent"), Some(AGENT));
+    assert_eq!(requests[0].body, "level=2&answer=1514285714300");
+    assert_eq!(requests[2].body, "level=1&answer=3068");
+}
+
+#[test]
+fn never_resubmits_a_known_answer() {
+    let server = MockServer::start(vec![(200, TOO_LOW), (200, RIGHT)]);
+    let website = Website::with_base_url(&server.url, "abc123", AGENT);
+    let path = book_path("resubmit");
+    let mut book = AnswerBook::load(&path).unwrap();
+
+    let submit = |book: &mut AnswerBook, answer: &str| submit::submit(book, &website, 2022, 1, Part::A, answer, true).unwrap();
+    assert_eq!(submit(&mut book, "100"), Submission::Sent(Response::Judged(Verdict::TooLow)));
+    assert_eq!(submit(&mut book, "100"), Submission::Refused(Refusal::AlreadyGuessed(Verdict::TooLow)));
+    assert_eq!(submit(&mut book, "200"), Submission::Sent(Response::Judged(Verdict::Right)));
+    assert_eq!(submit(&mut book, "300"), Submission::Refused(Refusal::AlreadySolved(String::from("200"))));
+    assert_eq!(server.requests().len(), 2);
+
+    //What was said is still known the next time
+    let book = AnswerBook::load(&path).unwrap();
+    assert_eq!(book.check(1, Part::A, "100"), Err(Refusal::AlreadySolved(String::from("200"))));
+    assert_eq!(book.guesses(1, Part::A).count(), 2);
+    assert_eq!(book.guesses(1, Part::B).count(), 0);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn flags_answers_outside_the_known_bounds() {
+    let path = book_path("bounds");
+    let mut book = AnswerBook::load(&path).unwrap();
+    let guesses = [(Verdict::TooLow, "10"), (Verdict::TooLow, "20"), (Verdict::TooHigh, "90"), (Verdict::TooHigh, "80")];
+    for (verdict, answer) in guesses {
+        book.record(Guess { day: 5, part: Part::B, verdict, answer: String::from(answer) }).unwrap();
+    }
+
+    assert_eq!(book.check(5, Part::B, "85"), Err(Refusal::AboveBound(String::from("80"))));
+    assert_eq!(book.check(5, Part::B, "15"), Err(Refusal::BelowBound(String::from("20"))));
+    assert_eq!(book.check(5, Part::B, "50"), Ok(()));
+    assert_eq!(book.check(5, Part::B, "ABCDEFGH"), Ok(()));
+    assert_eq!(book.check(5, Part::A, "85"), Ok(()));
+    assert!(Refusal::AboveBound(String::from("80")).can_force());
+    assert!(!Refusal::AlreadyGuessed(Verdict::TooHigh).can_force());
+
+    //Only forcing sends an out of bounds answer
+    let server = MockServer::start(vec![(200, TOO_HIGH)]);
+    let website = Website::with_base_url(&server.url, "abc123", AGENT);
+    let refused = submit::submit(&mut book, &website, 2022, 5, Part::B, "85", false).unwrap();
+    assert_eq!(refused, Submission::Refused(Refusal::AboveBound(String::from("80"))));
+    let forced = submit::submit(&mut book, &website, 2022, 5, Part::B, "85", true).unwrap();
+    assert_eq!(forced, Submission::Sent(Response::Judged(Verdict::TooHigh)));
+    assert_eq!(server.requests().len(), 1);
+
+    let contents = fs::read_to_string(&path).unwrap();
+    assert!(contents.starts_with('#'));
+    assert!(contents.ends_with("5 b too_low 10\n5 b too_low 20\n5 b too_high 90\n5 b too_high 80\n5 b too_high 85\n"));
+    fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,22 @@
+use std::time::Duration;
+
+use aoc_common::timing::Stats;
+
+fn stats_of(millis: &[u64]) -> Option<(u64, u64, u64)> {
+    Stats::of(millis.iter().map(|millis| Duration::from_millis(*millis)))
+        .map(|stats| (stats.min.as_millis() as u64, stats.median.as_millis() as u64, stats.max.as_millis() as u64))
+}
+
+#[test]
+fn stats_take_the_middle_run_or_the_mean_of_the_middle_two() {
+    //In any order
+    assert_eq!(stats_of(&[30, 10, 20]), Some((10, 20, 30)));
+    assert_eq!(stats_of(&[40, 10, 30, 20]), Some((10, 25, 40)));
+    assert_eq!(stats_of(&[7]), Some((7, 7, 7)));
+    assert_eq!(stats_of(&[5, 8]), Some((5, 6, 8)));
+    assert_eq!(stats_of(&[]), None);
+
+    //Half way between is rounded down to the nanosecond
+    let stats = Stats::of([Duration::from_nanos(1), Duration::from_nanos(4)].into_iter()).unwrap();
+    assert_eq!(stats.median, Duration::from_nanos(2));
+}
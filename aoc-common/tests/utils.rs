@@ -0,0 +1,151 @@
+use aoc_common::utils::{self, Cycle, Input, ScanError, Scanner};
+
+#[test]
+fn cycles_are_found_where_a_key_comes_round_again() {
+    //Doubling mod 20 from 3: 3, 6, 12, 4, 8, 16, 12, ...
+    let keys = std::iter::successors(Some(3), |n| Some(n * 2 % 20));
+    let cycle = utils::find_cycle(keys).unwrap();
+    assert_eq!(cycle, Cycle { start: 2, length: 4 });
+
+    assert_eq!(utils::find_cycle([1, 2, 3]), None);
+    assert_eq!(utils::find_cycle("abcb".chars()), Some(Cycle { start: 1, length: 2 }));
+}
+
+#[test]
+fn cycles_say_where_an_index_was_first_seen() {
+    let cycle = Cycle { start: 2, length: 4 };
+    assert_eq!((cycle.first_seen(1), cycle.times_round(1)), (1, 0));
+    assert_eq!((cycle.first_seen(5), cycle.times_round(5)), (5, 0));
+    assert_eq!((cycle.first_seen(6), cycle.times_round(6)), (2, 1));
+    assert_eq!((cycle.first_seen(1_000_000_001), cycle.times_round(1_000_000_001)), (5, 249_999_999));
+
+    //Working out a far off value of the sequence from the values up to the repeat
+    let values: Vec<u64> = std::iter::successors(Some(3), |n| Some(n * 2 % 20)).take(6).collect();
+    let index = 1_000_000_001;
+    assert_eq!(values[cycle.first_seen(index)], 16);
+}
+
+#[test]
+fn scanners_match_literals_word_by_word() {
+    let mut scanner = Scanner::new("Blueprint 1: Each ore robot costs 4 ore.");
+    scanner.expect_literal("Blueprint").unwrap();
+    assert_eq!(scanner.next_int::<u32>(), Ok(1));
+    //The words of the literal can be separated by any of the delimiters
+    scanner.expect_literal("Each  ore robot,").unwrap();
+
+    //Nothing is consumed by a literal that doesn't match, even if its first words do
+    let mark = scanner.mark();
+    let expected = ScanError::Unexpected { column: 37, expected: String::from("'clay'"), found: String::from("ore.") };
+    assert_eq!(scanner.expect_literal("costs 4 clay"), Err(expected));
+    assert_eq!(scanner.mark(), mark);
+    assert_eq!(scanner.peek(), Some("costs"));
+    let expected = ScanError::EndOfLine { column: 41, expected: String::from("'each'") };
+    assert_eq!(scanner.expect_literal("costs 4 ore. each"), Err(expected));
+    scanner.expect_literal("costs 4 ore.").unwrap();
+    assert!(scanner.is_at_end());
+}
+
+#[test]
+fn scanners_read_numbers_or_say_where_they_are_not() {
+    let mut scanner = Scanner::new("300 -1 x");
+    //Too big for the type, and left to be read again
+    let error = scanner.next_int::<u8>().unwrap_err();
+    assert_eq!(error, ScanError::Unexpected { column: 1, expected: String::from("a number"), found: String::from("300") });
+    assert_eq!(error.to_string(), "expected a number at column 1, found '300'");
+    assert_eq!(scanner.next_int::<u16>(), Ok(300));
+    assert_eq!(scanner.next_int::<u8>().map_err(|error| error.to_string()), Err(String::from("expected a number at column 5, found '-1'")));
+    assert_eq!(scanner.next_int::<i8>(), Ok(-1));
+    assert_eq!(scanner.next_int::<i32>().map_err(|error| error.to_string()), Err(String::from("expected a number at column 8, found 'x'")));
+    assert_eq!(scanner.next_token(), Ok("x"));
+    assert_eq!(scanner.next_int::<i32>(), Err(ScanError::EndOfLine { column: 9, expected: String::from("a number") }));
+}
+
+#[test]
+fn scanners_peek_and_rewind() {
+    let mut scanner = Scanner::new("a, b;c");
+    let start = scanner.mark();
+    assert_eq!(scanner.peek(), Some("a"));
+    assert_eq!(scanner.next_token(), Ok("a"));
+    assert_eq!(scanner.peek(), Some("b"));
+    assert_eq!(scanner.rest(), ", b;c");
+    let after_a = scanner.mark();
+    assert_eq!(scanner.by_ref().collect::<Vec<_>>(), ["b", "c"]);
+    assert!(scanner.is_at_end());
+    assert_eq!(scanner.peek(), None);
+
+    scanner.rewind(after_a);
+    assert_eq!(scanner.next_token(), Ok("b"));
+    scanner.rewind(start);
+    assert_eq!(scanner.collect::<Vec<_>>(), ["a", "b", "c"]);
+
+    //Delimiters of its own, '=' isn't one of the default ones
+    let mut scanner = Scanner::with_delimiters("x=-3, y=12", &['=', ',', ' ']);
+    scanner.expect_literal("x").unwrap();
+    assert_eq!(scanner.next_int::<i32>(), Ok(-3));
+    scanner.expect_literal("y=").unwrap();
+    assert_eq!(scanner.next_int::<i32>(), Ok(12));
+    assert!(scanner.is_at_end());
+    assert_eq!(Scanner::new("x=-3, y=12").next_token(), Ok("x=-3"));
+}
+
+#[test]
+fn all_the_ints_are_extracted() {
+    let ints = utils::extract_all_ints::<i32>("Sensor at x=-2, y=15: closest beacon is at x=3-4");
+    assert_eq!(ints, Ok(vec![-2, 15, 3, -4]));
+    //A '-' on its own isn't a number, and only the last of several counts
+    assert_eq!(utils::extract_all_ints::<i32>("a - b --5 -"), Ok(vec![-5]));
+    assert_eq!(utils::extract_all_ints::<u8>("no numbers"), Ok(vec![]));
+    assert!(utils::extract_all_ints::<u8>("x=-3").is_err());
+    assert!(utils::extract_all_ints::<u8>("x=300").is_err());
+}
+
+#[test]
+fn records_are_separated_by_any_number_of_blank_lines() {
+    //Blank lines before, between and after the records
+    let text = "\n\n1\n2\n\n\n  \n3\n\n4\n5\n\n";
+    let records: Vec<Vec<String>> = utils::records(Input::Text(text))
+        .unwrap()
+        .map(|record| record.unwrap().lines().to_vec())
+        .collect();
+    assert_eq!(records, [vec!["1", "2"], vec!["3"], vec!["4", "5"]]);
+    assert_eq!(utils::records(Input::Text("\n\n")).unwrap().count(), 0);
+
+    let sums = utils::try_process_records(
+        Input::Text(text),
+        |record| record.try_parse_lines(|line| line.parse::<u32>()).map(|numbers| numbers.iter().sum::<u32>()),
+        Vec::new(),
+        |mut sums, sum| {
+            sums.push(sum);
+            sums
+        },
+        |sums| sums,
+    );
+    assert_eq!(sums.unwrap(), [3, 3, 9]);
+}
+
+#[test]
+fn record_errors_give_the_line() {
+    let records: Vec<_> = utils::records(Input::Text("\n1\n2\n\n\n3\nx\n")).unwrap().map(Result::unwrap).collect();
+    //A line within the record, counting the blank lines before it
+    let error = records[1].try_parse_lines(|line| line.parse::<u32>()).unwrap_err();
+    assert_eq!(error.to_string(), "input:7: invalid digit found in string: 'x'");
+    //The record as a whole is at its first line
+    assert_eq!(records[1].error("not a pair").to_string(), "input:6: not a pair: '3'");
+    assert_eq!(records[0].try_parse_lines(|line| line.parse::<u32>()).unwrap(), [1, 2]);
+
+    let parse = |record: &utils::Record| record.try_parse_lines(|line| line.parse::<u32>());
+    let error = utils::try_process_records(Input::Text("1\n\n2\nx\n"), parse, 0, |count, _| count + 1, |count| count);
+    assert_eq!(error.unwrap_err().to_string(), "input:4: invalid digit found in string: 'x'");
+}
+
+#[test]
+fn sections_have_to_number_as_many_as_asked_for() {
+    let [drawing, moves] = utils::sections::<2>(Input::Text("\n.#\n#.\n\n\nR10\n")).unwrap();
+    assert_eq!(drawing.lines(), [".#", "#."]);
+    assert_eq!(moves.lines(), ["R10"]);
+
+    let error = utils::sections::<2>(Input::Text("a\n\nb\n\nc\n")).err().unwrap();
+    assert_eq!(error.to_string(), "input: expected 2 sections separated by blank lines, found 3");
+    let error = utils::sections::<3>(Input::Text("a\n\nb\n")).err().unwrap();
+    assert_eq!(error.to_string(), "input: expected 3 sections separated by blank lines, found 2");
+}
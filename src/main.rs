@@ -0,0 +1,277 @@
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::num::NonZeroUsize;
+use std::path::{self, Path, PathBuf};
+use std::process::ExitCode;
+use std::env;
+
+use clap::{Parser, Subcommand};
+
+use aoc_common::inputs::InputProvider;
+use aoc_common::report::{self, Format};
+use aoc_common::runner::{self, Day, Days, Outcome, Part, Run, Solution, Year};
+use aoc_common::submit::{self, AnswerBook, Response, Submission, Verdict};
+use aoc_common::website::Website;
+use aoc_common::{scaffold, utils};
+
+mod sandbox;
+
+#[derive(Parser)]
+#[command(about = "Advent of Code solutions")]
+struct Cli {
+    /// Year to use, the latest by default
+    #[arg(long, global = true)]
+    year: Option<u16>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+///Every year there are solutions for, latest last
+const YEARS: &[Year] = &[twenty_twenty_two::runner::YEAR];
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solutions for some or all of the days
+    Run {
+        /// Day(s) to run, e.g. 17, 1..10 or 1..=10 (may be repeated)
+        #[arg(long, required_unless_present = "all")]
+        day: Vec<Days>,
+        /// Only run this part
+        #[arg(long, value_enum)]
+        part: Option<Part>,
+        /// Run every day
+        #[arg(long, conflicts_with = "day")]
+        all: bool,
+        /// Input to use instead of each day's input.txt. A bare file name such as
+        /// test_input.txt is looked for in the day's directory, - reads stdin
+        #[arg(long)]
+        input: Option<String>,
+        /// Run each solution N times and report the min/median/max times
+        #[arg(long, value_name = "N")]
+        bench: Option<NonZeroUsize>,
+        /// How to report the results
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        /// Write the json or csv report to this file rather than stdout. The solutions print
+        /// their progress to stderr, so stdout only has the report either way.
+        #[arg(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+        /// Only use the inputs already saved, rather than fetching any that are missing with the
+        /// session in AOC_SESSION
+        #[arg(long)]
+        offline: bool,
+    },
+    /// Run a part and submit its answer with the session in AOC_SESSION, unless answer_book.txt
+    /// shows it to be wrong
+    Submit {
+        #[arg(long)]
+        day: Day,
+        #[arg(long, value_enum)]
+        part: Part,
+        /// Submit this rather than running the solution
+        #[arg(long)]
+        answer: Option<String>,
+        /// Submit even if the answer is outside the bounds given by earlier guesses that were
+        /// too high or too low
+        #[arg(long)]
+        force: bool,
+    },
+    /// List the registered solutions
+    List,
+    /// Start a new day from the year's template, registering it and creating its input files
+    NewDay {
+        day: Day,
+    },
+    /// Run whatever is currently in the sandbox
+    Sandbox,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let year = match cli.year {
+        None => YEARS.last().unwrap(),
+        Some(wanted) => match YEARS.iter().find(|year| year.year == wanted) {
+            Some(year) => year,
+            None => {
+                let years: Vec<String> = YEARS.iter().map(|year| year.year.to_string()).collect();
+                eprintln!("There are no solutions for {}, there are for {}", wanted, years.join(", "));
+                return ExitCode::FAILURE;
+            }
+        },
+    };
+    //Paths given on the command line are relative to where we are run from, so they need to be
+    // made absolute before moving into the year's directory where the inputs are
+    let command = match cli.command {
+        Command::Run { day, part, all, input, bench, format, output, offline } => Command::Run {
+            day,
+            part,
+            all,
+            input: input.map(absolute_input),
+            bench,
+            format,
+            output: output.map(|output| path::absolute(&output).unwrap_or(output)),
+            offline,
+        },
+        command => command,
+    };
+    if let Err(err) = env::set_current_dir(year.directory) {
+        eprintln!("Could not move to {}: {}", year.directory, err);
+        return ExitCode::FAILURE;
+    }
+
+    match command {
+        Command::Run { day, part, all, input, bench, format, output, offline } => {
+            let days = if all { vec![Days::all()] } else { day };
+            let solutions = runner::select(year.solutions, &days, part);
+            if solutions.is_empty() {
+                eprintln!("There are no solutions for those days in {}", year.year);
+                return ExitCode::FAILURE;
+            }
+            if input.is_none() {
+                fetch_inputs(year, &solutions, offline);
+            }
+            let runs = bench.map_or(1, NonZeroUsize::get);
+            return run(&solutions, input.as_deref(), runs, format, output);
+        }
+        Command::Submit { day, part, answer, force } => return submit(year, day, part, answer, force),
+        Command::List => {
+            for solution in year.solutions {
+                println!("{:<5} {}", solution.label(), solution.name);
+            }
+        }
+        Command::NewDay { day } => match scaffold::new_day(Path::new("."), &year.layout, day) {
+            Ok(changed) => {
+                for path in changed {
+                    println!("{}", path.display());
+                }
+            }
+            Err(error) => {
+                eprintln!("Could not create day {}: {}", day, error);
+                return ExitCode::FAILURE;
+            }
+        },
+        Command::Sandbox => sandbox::entry_point(),
+    }
+    ExitCode::SUCCESS
+}
+
+///Make sure that the inputs are saved, a day whose input can't be got will fail when it's run
+fn fetch_inputs(year: &Year, solutions: &[&Solution], offline: bool) {
+    let mut provider = InputProvider::new(year.year, Path::new(year.directory));
+    if !offline {
+        match Website::from_env() {
+            Ok(Some(website)) => provider = provider.with_fetcher(website),
+            Ok(None) => {}
+            Err(message) => eprintln!("Not fetching missing inputs: {}", message),
+        }
+    }
+    let mut days: Vec<Day> = solutions.iter().map(|solution| solution.day).collect();
+    days.dedup();
+    for day in days {
+        if let Err(error) = provider.input(day) {
+            eprintln!("No input for day {}: {}", day, error);
+        }
+    }
+}
+
+fn submit(year: &Year, day: Day, part: Part, answer: Option<String>, force: bool) -> ExitCode {
+    let website = match Website::from_env() {
+        Ok(Some(website)) => website,
+        Ok(None) => {
+            eprintln!("Set {} to the session cookie to submit answers", aoc_common::website::SESSION_ENV);
+            return ExitCode::FAILURE;
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+    let answer = match answer {
+        Some(answer) => answer,
+        None => match solve(year, day, part) {
+            Ok(answer) => answer,
+            Err(message) => {
+                eprintln!("{}", message);
+                return ExitCode::FAILURE;
+            }
+        },
+    };
+    let label = format!("{}{}", day, part);
+    let submitted = AnswerBook::load(Path::new(submit::ANSWER_BOOK))
+        .and_then(|mut book| submit::submit(&mut book, &website, year.year, day, part, &answer, force));
+    match submitted {
+        Ok(Submission::Sent(Response::Judged(Verdict::Right))) => {
+            println!("{} {} is right", label, answer);
+            return ExitCode::SUCCESS;
+        }
+        Ok(Submission::Sent(Response::Judged(verdict))) => println!("{} {} is {}", label, answer, verdict),
+        Ok(Submission::Sent(Response::Wait(left))) => println!("{} Answered too recently, wait {}", label, left),
+        Ok(Submission::Sent(Response::WrongLevel)) => println!("{} Not the part being solved, is it already done?", label),
+        Ok(Submission::Refused(refusal)) if refusal.can_force() => {
+            println!("{} Not submitting {}: {} (--force to submit it anyway)", label, answer, refusal)
+        }
+        Ok(Submission::Refused(refusal)) => println!("{} Not submitting {}: {}", label, answer, refusal),
+        Err(error) => eprintln!("{} Could not submit: {}", label, error),
+    }
+    ExitCode::FAILURE
+}
+
+///Run the part's solution for the answer to submit
+fn solve(year: &Year, day: Day, part: Part) -> Result<String, String> {
+    let solution = year
+        .solutions
+        .iter()
+        .find(|solution| solution.day == day && solution.part.matches(Some(part)))
+        .ok_or(format!("There is no solution for {}{}", day, part))?;
+    if solution.part == Part::Both {
+        return Err(format!("{} gives both parts at once, pass the part's answer with --answer", solution.label()));
+    }
+    fetch_inputs(year, &[solution], false);
+    let mut runs = runner::run(&[solution], None, 1, runner::print_run);
+    match runs.pop().map(|run| run.outcome) {
+        Some(Outcome::Solved { answer, .. }) => Ok(answer.value),
+        _ => Err(format!("{} has no answer to submit", solution.label())),
+    }
+}
+
+///A bare file name, e.g. test_input.txt, is left to be looked for in each day's directory
+fn absolute_input(input: String) -> String {
+    if input == utils::STDIN || Path::new(&input).components().count() == 1 {
+        return input;
+    }
+    path::absolute(&input)
+        .map(|input| input.display().to_string())
+        .unwrap_or(input)
+}
+
+fn run(solutions: &[&Solution], input: Option<&str>, runs: usize, format: Format, output: Option<PathBuf>) -> ExitCode {
+    if format == Format::Text && output.is_some() {
+        eprintln!("--output is only for the json and csv formats");
+        return ExitCode::FAILURE;
+    }
+    let results = runner::run(solutions, input, runs, |run| {
+        if format == Format::Text {
+            runner::print_run(run);
+        } else if let Outcome::Failed(error) = &run.outcome {
+            eprintln!("{} Failed: {}", run.solution.label(), error);
+        }
+    });
+    if format == Format::Text {
+        runner::print_timings(&results);
+    } else {
+        let written = match &output {
+            Some(path) => File::create(path)
+                .map_err(|err| format!("{}: {}", path.display(), err).into())
+                .and_then(|file| report::write(BufWriter::new(file), format, &results)),
+            None => report::write(io::stdout().lock(), format, &results),
+        };
+        if let Err(err) = written {
+            eprintln!("Could not write the report: {}", err);
+            return ExitCode::FAILURE;
+        }
+    }
+    if results.iter().any(Run::failed) {
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
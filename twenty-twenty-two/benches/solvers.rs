@@ -0,0 +1,101 @@
+//Benchmarks for the slow part of the heavier days, each is run on the day's real input with the
+//input read once up front so that only the solving is measured.
+//
+//The solvers print their progress as they go, so run with the output redirected, e.g.
+//cargo bench --bench solvers -- --save-baseline before > /dev/null
+//and then compare in the criterion report (target/criterion/report/index.html).
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+use twenty_twenty_two::utils::Input;
+use twenty_twenty_two::{nineteen, seventeen, sixteen, twenty, twentyfour, twentythree};
+
+fn day_16(c: &mut Criterion) {
+    let valves = sixteen::read_valves(Input::File("16/input.txt")).unwrap();
+    let mut group = c.benchmark_group("16 best_schedule");
+    group.sample_size(10);
+    group.bench_function("one agent", |b| {
+        b.iter(|| sixteen::best_schedule(black_box(&valves), 1, sixteen::MAX_TIME_1).unwrap())
+    });
+    group.bench_function("two agents", |b| {
+        b.iter(|| sixteen::best_schedule(black_box(&valves), 2, sixteen::MAX_TIME_2).unwrap())
+    });
+    group.finish();
+}
+
+fn day_17(c: &mut Criterion) {
+    let winds = seventeen::read_winds(Input::File("17/input.txt")).unwrap();
+    let chamber = seventeen::Chamber::standard();
+    c.bench_function("17 reduce2", |b| {
+        b.iter_batched(
+            || winds.clone(),
+            |winds| seventeen::reduce2(winds, &chamber, seventeen::NUM_ROCKS_2).unwrap(),
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+fn day_19(c: &mut Criterion) {
+    let blueprints = nineteen::read_blueprints(Input::File("19/input.txt")).unwrap();
+    let mut group = c.benchmark_group("19 best_builds");
+    group.sample_size(10);
+    group.bench_function("first blueprint 24 minutes", |b| {
+        b.iter(|| nineteen::best_builds(black_box(&blueprints[0]), 24))
+    });
+    group.bench_function("first blueprint 32 minutes", |b| {
+        b.iter(|| nineteen::best_builds(black_box(&blueprints[0]), 32))
+    });
+    group.bench_function("every blueprint 24 minutes", |b| {
+        b.iter(|| nineteen::best_builds_in_parallel(black_box(&blueprints), 24))
+    });
+    group.finish();
+}
+
+fn day_20(c: &mut Criterion) {
+    let numbers = twenty::read_decrypted_numbers(Input::File("20/input.txt")).unwrap();
+    c.bench_function("20 process2", |b| {
+        b.iter_batched(
+            || numbers.clone(),
+            |mut mixed| {
+                twenty::process2(&numbers, &mut mixed);
+                mixed
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+fn day_23(c: &mut Criterion) {
+    let elves = twentythree::read_elves(Input::File("23/input.txt")).unwrap();
+    let mut group = c.benchmark_group("23 spread");
+    group.sample_size(10);
+    //Part b, moving the elves until the first round in which none of them move
+    group.bench_function("until the elves stop", |b| {
+        b.iter_batched(
+            || elves.clone(),
+            |mut elves| twentythree::spread(&mut elves, usize::MAX),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+fn day_24(c: &mut Criterion) {
+    let valley = twentyfour::read_valley(Input::File("24/input.txt")).unwrap();
+    let (start, goal) = twentyfour::get_start_and_goal(&valley);
+    let mut group = c.benchmark_group("24 reduce");
+    group.sample_size(10);
+    group.bench_function("start to goal", |b| {
+        b.iter_batched(
+            || valley.clone(),
+            |mut valley| twentyfour::reduce(&mut valley, start, goal),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, day_16, day_17, day_19, day_20, day_23, day_24);
+criterion_main!(benches);
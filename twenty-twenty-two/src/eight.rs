@@ -1,31 +1,32 @@
-use std::io::Error;
-use std::convert::identity;
 use std::cmp;
+use crate::error::Error;
+use crate::grid::{self, DenseGrid, Pos, Scale};
 use crate::utils;
 
-
-const FILE_NAME: &str = "8/input.txt";
-
-pub fn _8a() -> Result<u64, Error>{
-    utils::process_file(
-        FILE_NAME,
-        identity,
+pub fn _8a(input: utils::Input) -> Result<u64, Error>{
+    utils::try_process_file(
+        input,
+        grid::row_parser(parse_height),
         Trees::new(),
         accumulator,
         reduce1
     )
 }
 
-pub fn _8b() -> Result<u64, Error> {
-    utils::process_file(
-        FILE_NAME,
-        identity,
+pub fn _8b(input: utils::Input) -> Result<u64, Error> {
+    utils::try_process_file(
+        input,
+        grid::row_parser(parse_height),
         Trees::new(),
         accumulator,
         reduce2
     )
 }
 
+fn parse_height(c: char) -> Result<i8, String> {
+    c.to_digit(10).map(|height| height as i8).ok_or_else(|| format!("'{}' isn't a tree height", c))
+}
+
 struct Tree {
     height: i8,
     highest_east: i8,
@@ -37,7 +38,7 @@ struct Tree {
 impl Tree {
     pub fn new(height: i8, highest_east: i8, highest_north: i8) -> Tree {
         Tree {
-            height: height,
+            height,
             highest_east,
             highest_west: -1,
             highest_north,
@@ -47,41 +48,41 @@ impl Tree {
 }
 
 struct Trees {
-    trees: Vec<Vec<Tree>>
+    trees: DenseGrid<Tree>
 }
 
 impl Trees {
     pub fn new() -> Trees {
         Trees {
-            trees: Vec::new(),
+            trees: DenseGrid::new(),
         }
     }
 }
 
-fn accumulator(mut trees: Trees, line: String) -> Trees {
-    let row_north = trees.trees.last();
+fn accumulator(mut trees: Trees, heights: Vec<i8>) -> Trees {
+    let height = trees.trees.height();
+    let row_north = (height > 0).then(|| trees.trees.row(height - 1));
     let mut highest_east: i8 = -1;
-    let mut tree_row: Vec<Tree> = Vec::with_capacity(line.len());
-    for (index, height_char) in line.chars().enumerate() {
-        let height = height_char.to_string().parse::<i8>().unwrap();
-        let highest_north = row_north.map(|vec| {
-            let north_tree = vec.get(index).unwrap();
+    let mut tree_row: Vec<Tree> = Vec::with_capacity(heights.len());
+    for (index, height) in heights.into_iter().enumerate() {
+        let highest_north = row_north.map(|row| {
+            let north_tree = &row[index];
             cmp::max(north_tree.highest_north, north_tree.height)
         }).unwrap_or(-1);
         let new_tree = Tree::new(height, highest_east, highest_north);
         highest_east = cmp::max(highest_east, new_tree.height);
         tree_row.push(new_tree);
     }
-    trees.trees.push(tree_row);
+    trees.trees.push_row(tree_row);
     trees
 }
 
 fn reduce1(trees: Trees) -> u64 {
     //first we assume all are visible (assuming a rectangular arrangement)
-    let width = trees.trees.first().unwrap().len();
-    let height = trees.trees.len();
+    let width = trees.trees.width();
+    let height = trees.trees.height();
 
-    let mut num_visible: u64 = trees.trees.len() as u64 * width as u64;
+    let mut num_visible: u64 = height as u64 * width as u64;
     //Now loop through calculating the highestWest and highestSouth as we go
     //When we find a tree that is not visible, decrement the num_visible
     let mut highest_souths: Vec<i8> = Vec::with_capacity(width);
@@ -89,16 +90,15 @@ fn reduce1(trees: Trees) -> u64 {
         highest_souths.push(-1)
     }
 
-    let mut trees_rows = trees.trees;
+    let mut trees_grid = trees.trees;
 
     for row_index in (0..height).rev() {
         let mut highest_west: i8 = -1;
-        let row = trees_rows.get_mut(row_index).unwrap();
 
         for col_index in (0..width).rev() {
             let highest_south = highest_souths.get_mut(col_index).unwrap();
 
-            let tree = row.get_mut(col_index).unwrap();
+            let tree = &mut trees_grid[Pos::new(col_index as Scale, row_index as Scale)];
             tree.highest_west = highest_west;
             tree.highest_south = *highest_south;
             highest_west = cmp::max(highest_west, tree.height);
@@ -118,8 +118,9 @@ fn reduce1(trees: Trees) -> u64 {
 
 fn reduce2(trees: Trees) -> u64 {
     //brute force it...  meh :(
-    let height = trees.trees.len();
-    let width = trees.trees.first().unwrap().len();
+    let height = trees.trees.height();
+    let width = trees.trees.width();
+    let height_at = |x: usize, y: usize| trees.trees[Pos::new(x as Scale, y as Scale)].height;
 
     let mut max_scenic_score: u64 = 0;
 
@@ -127,24 +128,22 @@ fn reduce2(trees: Trees) -> u64 {
     for row in 1..(height-1) {
         for column in 1..(width-1) {
 
-            let viewing_tree = trees.trees.get(row).unwrap().get(column).unwrap();
+            let viewing_height = height_at(column, row);
 
             let y = row;
             //look east
             let mut view_east = 0;
             for x in (0..column).rev() {
-                let visible_tree = trees.trees.get(y).unwrap().get(x).unwrap();
                 view_east += 1;
-                if visible_tree.height >= viewing_tree.height {
+                if height_at(x, y) >= viewing_height {
                     break;
                 }
             }
             //look west
             let mut view_west = 0;
             for x in (column + 1)..width {
-                let visible_tree = trees.trees.get(y).unwrap().get(x).unwrap();
                 view_west += 1;
-                if visible_tree.height >= viewing_tree.height {
+                if height_at(x, y) >= viewing_height {
                     break;
                 }
             }
@@ -153,18 +152,16 @@ fn reduce2(trees: Trees) -> u64 {
             //look north
             let mut view_north = 0;
             for y in (0..row).rev() {
-                let visible_tree = trees.trees.get(y).unwrap().get(x).unwrap();
                 view_north += 1;
-                if visible_tree.height >= viewing_tree.height {
+                if height_at(x, y) >= viewing_height {
                     break;
                 }
             }
             //look south
             let mut view_south = 0;
             for y in (row+1)..height {
-                let visible_tree = trees.trees.get(y).unwrap().get(x).unwrap();
                 view_south += 1;
-                if visible_tree.height >= viewing_tree.height {
+                if height_at(x, y) >= viewing_height {
                     break;
                 }
             }
@@ -1,77 +1,31 @@
-use std::fmt::Display;
-use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashSet};
 
+use crate::error::{Error, ParseResult};
+use crate::point::{Point2, Point3};
+use crate::search;
 use crate::utils;
 
-const FILE_NAME: &str = "18/input.txt";
-// const FILE_NAME: &str = "18/test_input.txt";
-// const FILE_NAME: &str = "18/my_testing_input.txt";
-
-pub fn _18a() -> Result<u32, std::io::Error> {
-    utils::process_file(FILE_NAME, parse_line, State::new(), accumulate, reduce1)
+pub fn _18a(input: utils::Input) -> Result<u32, Error> {
+    utils::try_process_file(input, parse_line, State::new(), accumulate, reduce1)
 }
 
-pub fn _18b() -> Result<u32, std::io::Error> {
-    utils::process_file(FILE_NAME, parse_line, State::new(), accumulate, reduce2)
+pub fn _18b(input: utils::Input) -> Result<u32, Error> {
+    utils::try_process_file(input, parse_line, State::new(), accumulate, reduce2)
 }
 
 type CoordScale = i32;
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct Coord3 {
-    x: CoordScale,
-    y: CoordScale,
-    z: CoordScale,
-}
-
-impl Coord3 {
-    pub fn new(x: CoordScale, y: CoordScale, z: CoordScale) -> Coord3 {
-        Coord3 { x, y, z }
-    }
-
-    pub fn neighbours(&self) -> [Coord3; 6] {
-        [
-            Coord3::new(self.x + 1, self.y, self.z),
-            Coord3::new(self.x - 1, self.y, self.z),
-            Coord3::new(self.x, self.y + 1, self.z),
-            Coord3::new(self.x, self.y - 1, self.z),
-            Coord3::new(self.x, self.y, self.z + 1),
-            Coord3::new(self.x, self.y, self.z - 1),
-        ]
-    }
-}
-
-impl Display for Coord3 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({},{},{})", self.x, self.y, self.z)
-    }
-}
-
-fn parse_line(line: String) -> Coord3 {
-    let mut chars = line.chars();
-    let x: CoordScale = utils::parse_next_number(&mut chars).unwrap();
-    let y: CoordScale = utils::parse_next_number(&mut chars).unwrap();
-    let z: CoordScale = utils::parse_next_number(&mut chars).unwrap();
-    Coord3::new(x, y, z)
-}
-
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct Coord2 {
-    _1: CoordScale,
-    _2: CoordScale,
-}
+type Coord3 = Point3<CoordScale>;
 
-impl Coord2 {
-    pub fn new(_1: CoordScale, _2: CoordScale) -> Coord2 {
-        Coord2 { _1, _2 }
+fn parse_line(line: &str) -> ParseResult<Coord3> {
+    match utils::extract_all_ints(line)?[..] {
+        [x, y, z] => Ok(Coord3::new(x, y, z)),
+        _ => Err("expected three coordinates".into()),
     }
 }
 
-impl Display for Coord2 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({},{})", self._1, self._2)
-    }
-}
+///A cube's coordinates along the two axes other than the one it is being sliced by
+type Coord2 = Point2<CoordScale>;
 
 struct State {
     all_cube_coords: HashSet<Coord3>,
@@ -92,31 +46,31 @@ impl State {
 }
 
 fn accumulate(mut state: State, coord: Coord3) -> State {
-    //println!("{}", coord);
-    let coords_z = state.xy_coords_by_z.entry(coord.z).or_insert(HashSet::new());
+    //eprintln!("{}", coord);
+    let coords_z = state.xy_coords_by_z.entry(coord.z).or_default();
     coords_z.insert(Coord2::new(coord.x,coord.y));
-    let coords_y = state.xz_coords_by_y.entry(coord.y).or_insert(HashSet::new());
+    let coords_y = state.xz_coords_by_y.entry(coord.y).or_default();
     coords_y.insert(Coord2::new(coord.x, coord.z));
-    let coords_x = state.yz_coords_by_x.entry(coord.x).or_insert(HashSet::new());
+    let coords_x = state.yz_coords_by_x.entry(coord.x).or_default();
     coords_x.insert(Coord2::new(coord.y, coord.z));
     state.all_cube_coords.insert(coord);
     state
 }
 
 fn xy_by_z_coord3_builder(coord: &Coord2, slice: CoordScale) -> Coord3 {
-    Coord3::new(coord._1, coord._2, slice)
+    Coord3::new(coord.x, coord.y, slice)
 }
 
 fn xz_by_y_coord3_builder(coord: &Coord2, slice: CoordScale) -> Coord3 {
-    Coord3::new(coord._1, slice, coord._2)
+    Coord3::new(coord.x, slice, coord.y)
 }
 
 fn yz_by_x_coord3_builder(coord: &Coord2, slice: CoordScale) -> Coord3 {
-    Coord3::new(slice, coord._1, coord._2)
+    Coord3::new(slice, coord.x, coord.y)
 }
 
 fn reduce(state: &State) -> u32 {
-    println!("There are {} coords", state.all_cube_coords.len());
+    eprintln!("There are {} coords", state.all_cube_coords.len());
 
     let mut num_faces = count_faces(
         &state.all_cube_coords,
@@ -246,13 +200,30 @@ fn reduce2(state: State) -> u32 {
         *state.xy_coords_by_z.last_key_value().unwrap().0,
     );
 
-    let mut visited_coords: HashSet<Coord3> = HashSet::new();
+    //the air outside of the droplet is everything that can be reached from just outside the bounds
+    let around = Bounds::new(
+        bounds.min_x - 1,
+        bounds.max_x + 1,
+        bounds.min_y - 1,
+        bounds.max_y + 1,
+        bounds.min_z - 1,
+        bounds.max_z + 1,
+    );
+    let outside_air = search::reachable(Coord3::new(around.min_x, around.min_y, around.min_z), |coord| {
+        coord.neighbours6().into_iter()
+            .filter(|co| !out_of_bounds(&around, co) && !state.all_cube_coords.contains(co))
+    });
+    eprintln!("There are {} coords of air around the droplet", outside_air.len());
+
+    //any other air is trapped in a bubble
     let mut all_bubble_coords: HashSet<Coord3> = HashSet::new();
-    for x in state.yz_coords_by_x.keys() {
-        for y in state.xz_coords_by_y.keys() {
-            for z in state.xy_coords_by_z.keys() {
-                let cube_coord = Coord3::new(*x, *y, *z);
-                look_for_an_escape(&state, &bounds, &mut visited_coords, &mut all_bubble_coords, cube_coord);
+    for x in bounds.min_x..=bounds.max_x {
+        for y in bounds.min_y..=bounds.max_y {
+            for z in bounds.min_z..=bounds.max_z {
+                let coord = Coord3::new(x, y, z);
+                if !state.all_cube_coords.contains(&coord) && !outside_air.contains_key(&coord) {
+                    all_bubble_coords.insert(coord);
+                }
             }
         }
     }
@@ -263,69 +234,11 @@ fn reduce2(state: State) -> u32 {
         bubble_state = accumulate(bubble_state, coord);
     }
 
-    for coord in state.all_cube_coords.intersection(&bubble_state.all_cube_coords) {
-        println!("!!Intesection: {}!!", coord);
-    }
-
     let bubble_faces = reduce(&bubble_state);
 
     all_faces - bubble_faces
 }
 
-///Look for a way to escape the shape - if it is not possible to escape, then add to the bubble coords (and anything else in this bubble)
-fn look_for_an_escape(
-    state: &State,
-    bounds: &Bounds,
-    visited_coords: &mut HashSet<Coord3>,
-    bubble_coords: &mut HashSet<Coord3>,
-    coord: Coord3
-) {
-    //Quick exit if we've already been here
-    if visited_coords.contains(&coord) {
-        return;
-    }
-    //Quick exit if this is a populated cube - we're only interested in spaces
-    if state.all_cube_coords.contains(&coord) {
-        return;
-    }
-    //not populated, look for an escape route
-    let mut stack: VecDeque<Coord3> = VecDeque::new();
-    let mut bubble_candidates: HashSet<Coord3> = HashSet::new();
-    let mut escaped = false;
-    visited_coords.insert(coord);
-    stack.push_front(coord);
-    while !stack.is_empty() {
-        let coord = stack.pop_front().unwrap();
-
-        if out_of_bounds(&bounds, &coord) {
-            //escaped!
-            escaped = true;
-            continue;
-        }
-
-        if !escaped {
-            //not escaped yet, it's a bubble candidate
-            bubble_candidates.insert(coord);
-        }
-
-        //visit the empty neighbours, we've not visited yet
-        coord.neighbours().iter()
-            .filter(|co| !state.all_cube_coords.contains(*co))
-            .for_each(|co| {
-                if !visited_coords.contains(co) {
-                    visited_coords.insert(*co);
-                    stack.push_front(*co);
-                }
-            });
-    }
-
-    if !escaped {
-        for coord in bubble_candidates.drain() {
-            bubble_coords.insert(coord);
-        }
-    }
-}
-
 fn out_of_bounds(bounds: &Bounds, coord: &Coord3) -> bool {
     coord.x > bounds.max_x || coord.x < bounds.min_x ||
     coord.y > bounds.max_y || coord.y < bounds.min_y ||
@@ -1,34 +1,32 @@
 use core::fmt;
-use std::io::Error;
 use std::collections::{VecDeque, BTreeMap};
 
 use substring::Substring;
 
-use crate::utils::process_file;
-
-const FILENAME: &str = "11/input.txt";
+use crate::error::{Error, ParseResult};
+use crate::utils::{try_process_records, Input, Record};
 
 // type WorryLevel = u128;
 // type WorryLevel = u64;
 type WorryLevel = usize;
 
-pub fn _11a() -> Result<WorryLevel, Error> {
-    process_file(
-        FILENAME,
-        parse,
-        State::new(),
-        accumulate,
-        calculate_monkey_business1,
-    )
+pub fn _11a(input: Input) -> Result<WorryLevel, Error> {
+    monkey_business(input, 20, 3)
+}
+
+pub fn _11b(input: Input) -> Result<WorryLevel, Error> {
+    monkey_business(input, 10000, 1)
 }
 
-pub fn _11b() -> Result<WorryLevel, Error> {
-    process_file(
-        FILENAME,
-        parse,
+///The product of the two highest numbers of inspections after the rounds, with worry levels
+/// divided by worry_level_post_inspection_divisor after each inspection
+pub fn monkey_business(input: Input, num_rounds: usize, worry_level_post_inspection_divisor: WorryLevel) -> Result<WorryLevel, Error> {
+    try_process_records(
+        input,
+        parse_notes,
         State::new(),
         accumulate,
-        calculate_monkey_business2,
+        |state| calculate_monkey_business(state, num_rounds, worry_level_post_inspection_divisor),
     )
 }
 
@@ -100,7 +98,7 @@ struct MonkeyUnderConstruction {
 impl MonkeyUnderConstruction {
     pub fn new(id: usize) -> MonkeyUnderConstruction {
         MonkeyUnderConstruction {
-            id: id,
+            id,
             item_worry_levels: None,
             operation: None,
             test_divisor: None,
@@ -109,27 +107,26 @@ impl MonkeyUnderConstruction {
         }
     }
 
-    pub fn construct_monkey(self) -> Monkey {
-        Monkey::new(
+    ///Construct the monkey, or None if any of the notes were missing
+    pub fn construct_monkey(self) -> Option<Monkey> {
+        Some(Monkey::new(
             self.id,
-            self.item_worry_levels.unwrap(),
-            self.operation.unwrap(),
-            self.test_divisor.unwrap(),
-            self.true_monkey_id.unwrap(),
-            self.false_monkey_id.unwrap()
-        )
+            self.item_worry_levels?,
+            self.operation?,
+            self.test_divisor?,
+            self.true_monkey_id?,
+            self.false_monkey_id?
+        ))
     }
 }
 
 struct State {
-    monkey_under_construction: Option<MonkeyUnderConstruction>,
     monkeys: BTreeMap<usize, Monkey>,
 }
 
 impl State {
     pub fn new() -> State {
         State {
-            monkey_under_construction: None,
             monkeys: BTreeMap::new(),
         }
     }
@@ -142,10 +139,29 @@ enum Command {
     Test{ divisor: WorryLevel },
     TrueMonkey{ id: usize },
     FalseMonkey{ id: usize },
-    MakeMonkey,
 }
 
-fn parse(line: String) -> Command {
+///Each record has the notes on one monkey, starting with its id
+fn parse_notes(notes: &Record) -> Result<Monkey, Error> {
+    let mut commands = notes.try_parse_lines(parse)?.into_iter();
+    let Some(Command::NewMonkey { id }) = commands.next() else {
+        return Err(notes.error("Notes should start with the monkey's id"));
+    };
+    let mut under_construction = MonkeyUnderConstruction::new(id);
+    for command in commands {
+        match command {
+            Command::NewMonkey { .. } => return Err(notes.error("Notes for two monkeys without a blank line between")),
+            Command::StartingItems { item_worry_levels } => under_construction.item_worry_levels = Some(VecDeque::from(item_worry_levels)),
+            Command::Operation { op } => under_construction.operation = Some(op),
+            Command::Test { divisor } => under_construction.test_divisor = Some(divisor),
+            Command::TrueMonkey { id } => under_construction.true_monkey_id = Some(id),
+            Command::FalseMonkey { id } => under_construction.false_monkey_id = Some(id),
+        }
+    }
+    under_construction.construct_monkey().ok_or_else(|| notes.error("Notes are missing something about the monkey"))
+}
+
+fn parse(line: &str) -> ParseResult<Command> {
     let first_five = line.substring(0, 5);
     match first_five {
         "Monke" => parse_monkey(line),
@@ -153,120 +169,73 @@ fn parse(line: String) -> Command {
         "  Ope" => parse_operation(line),
         "  Tes" => parse_test(line),
         "    I" => parse_true_or_false_monkey(line),
-        _ => Command::MakeMonkey,
+        _ => Err("Unrecognised note".into()),
     }
 }
 
-fn parse_monkey(line: String) -> Command {
+fn parse_monkey(line: &str) -> ParseResult<Command> {
     //Monkey id:
     let id_string = line.substring(7, line.len() - 1);
-    Command::NewMonkey {
-        id: id_string.parse().unwrap()
-    }
+    Ok(Command::NewMonkey {
+        id: id_string.parse()?
+    })
 }
 
-fn parse_starting_items(line: String) -> Command {
+fn parse_starting_items(line: &str) -> ParseResult<Command> {
     //  Starting items: item, item, item, item
     let worry_level_strings = line.substring(18, line.len());
     let mut item_worry_levels: Vec<WorryLevel> = Vec::new();
-    for worry_level in worry_level_strings.split(", ").into_iter() {
-        item_worry_levels.push(worry_level.parse().unwrap());
+    for worry_level in worry_level_strings.split(", ") {
+        item_worry_levels.push(worry_level.parse()?);
     }
-    Command::StartingItems { item_worry_levels }
+    Ok(Command::StartingItems { item_worry_levels })
 }
 
-fn parse_operation(line: String) -> Command {
+fn parse_operation(line: &str) -> ParseResult<Command> {
     //  Operation: new = old * value
     //or
     //  Operation: new = old + value
     let op_string = line.substring(23, 24);
     let value_string = line.substring(25, line.len());
     match (op_string, value_string) {
-        ("*", "old") => Command::Operation { op: Operation::Square },
-        ("*", _) => Command::Operation{ op: Operation::Multiply { value: value_string.parse().unwrap() }},
-        ("+", _) => Command::Operation{ op: Operation::Add { value: value_string.parse().unwrap() }},
-        _ => panic!("Unrecognised Operation: {}", line),
+        ("*", "old") => Ok(Command::Operation { op: Operation::Square }),
+        ("*", _) => Ok(Command::Operation{ op: Operation::Multiply { value: value_string.parse()? }}),
+        ("+", _) => Ok(Command::Operation{ op: Operation::Add { value: value_string.parse()? }}),
+        _ => Err("Unrecognised Operation".into()),
     }
 }
 
-fn parse_test(line: String) -> Command {
+fn parse_test(line: &str) -> ParseResult<Command> {
     //  Test: divisible by value
     let value_string = line.substring(21, line.len());
-    Command::Test { divisor: value_string.parse().unwrap() }
+    Ok(Command::Test { divisor: value_string.parse()? })
 }
 
-fn parse_true_or_false_monkey(line: String) -> Command {
+fn parse_true_or_false_monkey(line: &str) -> ParseResult<Command> {
     //    If true: throw to monkey 3
     //    If false: throw to monkey 0
     let if_string = line.substring(7, line.len());
-    let splitted: Vec<&str> = if_string.split(|c| c == ' ' || c == ':').collect();
-    let true_or_false = splitted.first().unwrap();
-    let id_string = splitted.last().unwrap();
-    match *true_or_false {
-        "true" => Command::TrueMonkey { id: id_string.parse().unwrap() },
-        "false" => Command::FalseMonkey { id: id_string.parse().unwrap() },
-        _ => panic!("Unrecognised If line: {}", line),
-    }
-}
-
-fn accumulate(mut state: State, command: Command) -> State {
-    match command {
-        Command::NewMonkey { id } => {
-            //println!("New Monkey under construction: {}", id);
-            state.monkey_under_construction = Some(MonkeyUnderConstruction::new(id));
-            state
-        },
-        Command::StartingItems { item_worry_levels } => {
-            state.monkey_under_construction.as_mut().unwrap().item_worry_levels = Some(VecDeque::from(item_worry_levels));
-            state
-        },
-        Command::Operation { op } => {
-            state.monkey_under_construction.as_mut().unwrap().operation = Some(op);
-            state
-        }
-        Command::Test { divisor } => {
-            state.monkey_under_construction.as_mut().unwrap().test_divisor = Some(divisor);
-            state
-        },
-        Command::TrueMonkey { id } => {
-            state.monkey_under_construction.as_mut().unwrap().true_monkey_id = Some(id);
-            state
-        },
-        Command::FalseMonkey { id } => {
-            state.monkey_under_construction.as_mut().unwrap().false_monkey_id = Some(id);
-            state
-        },
-        Command::MakeMonkey => {
-            let under_construction = state.monkey_under_construction.take().unwrap();
-            let monkey = under_construction.construct_monkey();
-            //println!("{}", monkey);
-            state.monkeys.insert(monkey.id, monkey);
-            state
-        }
+    let (true_or_false, throw) = if_string.split_once(':').ok_or("Unrecognised If line")?;
+    let id_string = throw.trim().strip_prefix("throw to monkey ").ok_or("Unrecognised If line")?;
+    match true_or_false {
+        "true" => Ok(Command::TrueMonkey { id: id_string.parse()? }),
+        "false" => Ok(Command::FalseMonkey { id: id_string.parse()? }),
+        _ => Err("Unrecognised If line".into()),
     }
 }
 
-fn calculate_monkey_business1(state: State) -> WorryLevel {
-    calculate_monkey_business(state, 20, 3)
-}
-
-fn calculate_monkey_business2(state: State) -> WorryLevel {
-    calculate_monkey_business(state, 10000, 1)
+fn accumulate(mut state: State, monkey: Monkey) -> State {
+    //eprintln!("{}", monkey);
+    state.monkeys.insert(monkey.id, monkey);
+    state
 }
 
 fn calculate_monkey_business(mut state: State, num_rounds: usize, worry_level_post_inspection_divisor: WorryLevel) -> WorryLevel {
-    //if there is a monkey still under construction, ensure we finish its construction now
-    state = if state.monkey_under_construction.is_some() {
-        accumulate(state, Command::MakeMonkey)
-    } else {
-        state
-    };
-
     //work out what we can use to mod any value we keep - we can take the mod as each of our divisors multiplied together.
     //and still keep the true/false modulo semantics
     let mod_worry_level: WorryLevel = state.monkeys.values()
         .map(|monkey| monkey.test_divisor)
-        .fold(1, |acc, divisor| acc * divisor);
+        .product();
 
     //now run the monkey business...
     state = perform_rounds(state, num_rounds, worry_level_post_inspection_divisor, mod_worry_level);
@@ -276,7 +245,7 @@ fn calculate_monkey_business(mut state: State, num_rounds: usize, worry_level_po
     collections.sort();
     collections.reverse();
     collections.truncate(2);
-    collections.iter().fold(1, |acc, item| acc * item)
+    collections.iter().product()
 }
 
 fn perform_rounds(mut state: State,
@@ -285,7 +254,7 @@ fn perform_rounds(mut state: State,
                   mod_worry_level: WorryLevel) -> State {
     let monkey_ids: Vec<_> = state.monkeys.keys().cloned().collect();
     for _round in 0..num_rounds {
-        //println!("=== Round {} ===", _round);
+        //eprintln!("=== Round {} ===", _round);
         for monkey_id in monkey_ids.iter() {
             //extract the worry levels from the source monkey (and update)
             let monkey = state.monkeys.get_mut(monkey_id).unwrap();
@@ -304,12 +273,12 @@ fn perform_rounds(mut state: State,
                 };
                 let worry_level_after_inspection = worry_level_during_inspection / worry_level_post_inspection_divisor;
                 let worry_level_after_inspection = worry_level_after_inspection % mod_worry_level;
-                let destination_monkey_id = if worry_level_after_inspection % monkey_copy.test_divisor == 0 {
+                let destination_monkey_id = if worry_level_after_inspection.is_multiple_of(monkey_copy.test_divisor) {
                     monkey_copy.true_monkey_id
                 } else {
                     monkey_copy.false_monkey_id
                 };
-                //println!("Monkey {} pushes {} ({} {} / 3) to monkey {}",
+                //eprintln!("Monkey {} pushes {} ({} {} / 3) to monkey {}",
                 //    monkey_id, worry_level_after_inspection, worry_level, monkey_copy.operation, destination_monkey_id);
                 let dest_monkey = state.monkeys.get_mut(&destination_monkey_id).unwrap();
                 dest_monkey.item_worry_levels.push_back(worry_level_after_inspection);
@@ -1,33 +1,45 @@
 use core::panic;
 use std::cmp::{min, max};
 
+use crate::error::{Error, ParseResult};
 use crate::utils;
 
-const FILE_NAME: &str = "15/input.txt";
-// const FILE_NAME: &str = "15/test_input.txt";
-// const FILE_NAME: &str = "15/tiny_input.txt";
+///The row to check in part a, and the largest x and y the beacon could be at in part b, for the
+/// real input (the example uses 10 and 20)
+const ROW: Scale = 2_000_000;
+const MAX_X_Y: Scale = 4_000_000;
 
-pub fn _15a() -> Result<i64, std::io::Error> {
-    utils::process_file(
-        FILE_NAME,
+pub fn _15a(input: utils::Input) -> Result<i64, Error> {
+    count_non_covered_positions_in_row(input, ROW)
+}
+
+pub fn _15b(input: utils::Input) -> Result<i64, Error> {
+    find_tuning_frequency(input, MAX_X_Y)
+}
+
+///Count the positions in the row where a beacon can't be
+pub fn count_non_covered_positions_in_row(input: utils::Input, row: Scale) -> Result<i64, Error> {
+    utils::try_process_file(
+        input,
         parse_line,
         State::new(),
         accumulate,
-        reduce1
+        |state| count_non_covered_positions(&state, row)
     )
 }
 
-pub fn _15b() -> Result<i64, std::io::Error> {
-    utils::process_file(
-        FILE_NAME,
+///Find the only position, with x and y between 0 and max_x_y, that isn't covered by a sensor
+pub fn find_tuning_frequency(input: utils::Input, max_x_y: Scale) -> Result<i64, Error> {
+    utils::try_process_file(
+        input,
         parse_line,
         State::new(),
         accumulate,
-        reduce2
+        |state| reduce2(&state, max_x_y)
     )
 }
 
-type Scale = i64;
+pub type Scale = i64;
 
 struct SensorAndBeacon {
     sensor_x: Scale,
@@ -63,7 +75,7 @@ impl SensorAndBeacon {
     /// Some(x2, beacon): if (x,y) is covered:
     ///   where x2 is the max x that is covered at row y
     ///   if beacon = true, then the beacon is in one of the squares
-    pub fn covers(self: &Self, x: Scale, y: Scale) -> Option<(Scale, bool)> {
+    pub fn covers(&self, x: Scale, y: Scale) -> Option<(Scale, bool)> {
         if x < self.min_coverage_x || x > self.max_coverage_x {
             return None;
         }
@@ -90,19 +102,19 @@ impl SensorAndBeacon {
 
 }
 
-fn parse_line(line: String) -> SensorAndBeacon {
+fn parse_line(line: &str) -> ParseResult<SensorAndBeacon> {
     //Example line:
     //Sensor at x=2389280, y=2368338: closest beacon is at x=2127703, y=2732666
-    let mut chars = line.chars();
-    utils::skip(&mut chars, 12); //"Sensor at x="
-    let sensor_x = utils::parse_next_number(&mut chars).unwrap(); //will parse up to ","
-    utils::skip(&mut chars, 3); //" y="
-    let sensor_y = utils::parse_next_number(&mut chars).unwrap(); //will parse up to ":"
-    utils::skip(&mut chars, 24); //" closest beacon is at x="
-    let beacon_x = utils::parse_next_number(&mut chars).unwrap(); //will parse up to ","
-    utils::skip(&mut chars, 3);//" y="
-    let beacon_y = utils::parse_next_number(&mut chars).unwrap();//eof
-    SensorAndBeacon::new(sensor_x, sensor_y, beacon_x, beacon_y)
+    let mut scanner = utils::Scanner::with_delimiters(line, &[' ', '=', ',', ':']);
+    scanner.expect_literal("Sensor at x=")?;
+    let sensor_x = scanner.next_int()?;
+    scanner.expect_literal("y=")?;
+    let sensor_y = scanner.next_int()?;
+    scanner.expect_literal("closest beacon is at x=")?;
+    let beacon_x = scanner.next_int()?;
+    scanner.expect_literal("y=")?;
+    let beacon_y = scanner.next_int()?;
+    Ok(SensorAndBeacon::new(sensor_x, sensor_y, beacon_x, beacon_y))
 }
 
 #[derive(Debug)]
@@ -148,27 +160,20 @@ fn accumulate(mut state: State, sensor_and_beacon: SensorAndBeacon) -> State {
 }
 
 // fn output_state(state: &State) {
-//     println!("");
-//     println!("{:?}", state.bounds);
+//     eprintln!("");
+//     eprintln!("{:?}", state.bounds);
 //     for y in state.bounds.min_y..(state.bounds.max_y + 1) {
 //         for x in state.bounds.min_x..(state.bounds.max_x + 1) {
 //             match state.sensors.get(0).unwrap().covers(x, y) {
-//                 Some(_) => print!("#"),
-//                 None => print!("."),
+//                 Some(_) => eprint!("#"),
+//                 None => eprint!("."),
 //             }
 //         }
-//         println!("");
+//         eprintln!("");
 //     }
-//     println!("");
+//     eprintln!("");
 // }
 
-fn reduce1(state: State) -> i64 {
-    // output_state(&state);
-    count_non_covered_positions(&state, 2_000_000)
-    // count_non_covered_positions(&state, 10)
-    // count_non_covered_positions(&state, 2)
-}
-
 fn count_non_covered_positions(state: &State, row: Scale) -> i64 {
     let mut count = 0;
     let mut x = state.bounds.min_x;
@@ -189,22 +194,20 @@ fn count_non_covered_positions(state: &State, row: Scale) -> i64 {
     count
 }
 
-const MAX_X_Y: i64 = 4_000_000;
-
-fn reduce2(state: State) -> i64 {
-    for y in 0..(MAX_X_Y + 1) {
+fn reduce2(state: &State, max_x_y: Scale) -> i64 {
+    for y in 0..(max_x_y + 1) {
         if y % 1000 == 0 {
-            println!("{}", y);
+            eprintln!("{}", y);
         }
         let mut x = 0;
-        'x_loop: while x <= (MAX_X_Y + 1) {
+        'x_loop: while x <= (max_x_y + 1) {
             for sensor in state.sensors.iter() {
                 if let Some((max_x_covered, _)) = sensor.covers(x, y) {
                     x = max_x_covered + 1;
                     continue 'x_loop;
                 }
             }
-            println!("({},{})", x, y);
+            eprintln!("({},{})", x, y);
             return x * 4_000_000 + y;
             // x += 1;
         }
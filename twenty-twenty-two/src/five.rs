@@ -1,38 +1,44 @@
 use lazy_static::lazy_static;
 use regex::Regex;
 
-use crate::utils;
-use std::io::Error;
+use crate::error::{Error, ParseResult};
+use crate::utils::{self, Record};
 use std::collections::{HashMap, BTreeMap};
 
+pub fn _5a(input: utils::Input) -> Result<String, Error> {
+    rearrange(input, move1)
+}
 
-pub fn _5a() -> Result<String, Error> {
-    let filename = "5/input.txt";
-    utils::process_file(
-        filename,
-        parse_line,
-        CrateStacks::new(),
-        accumulate1,
-        reduce,
-    )
+pub fn _5b(input: utils::Input) -> Result<String, Error> {
+    rearrange(input, move2)
 }
 
-pub fn _5b() -> Result<String, Error> {
-    let filename = "5/input.txt";
-    utils::process_file(
-        filename,
-        parse_line,
-        CrateStacks::new(),
-        accumulate2,
-        reduce,
-    )
+///The input is a drawing of the stacks, then after a blank line the moves to make
+fn rearrange(input: utils::Input, move_func: fn (CrateStacks, usize, usize, usize) -> CrateStacks) -> Result<String, Error> {
+    let [drawing, moves] = utils::sections(input)?;
+    let mut crate_stacks = parse_drawing(&drawing)?;
+    //Each move is checked against the stacks as they are when it is made
+    moves.try_parse_lines(|line| -> ParseResult<()> {
+        let Move { num, from, to } = parse_move_line(line)?;
+        let Some(crates) = crate_stacks.stacks.get(&from).map(Vec::len) else {
+            return Err(format!("there's no stack {}", from).into());
+        };
+        if !crate_stacks.stacks.contains_key(&to) {
+            return Err(format!("there's no stack {}", to).into());
+        }
+        if num > crates {
+            return Err(format!("stack {} only has {} crates", from, crates).into());
+        }
+        crate_stacks = move_func(std::mem::replace(&mut crate_stacks, CrateStacks::new()), num, from, to);
+        Ok(())
+    })?;
+    Ok(reduce(crate_stacks))
 }
 
-enum Line {
-    Empty,
-    Crates { crates_by_row: HashMap<usize, char>, },
-    Rows { rows: Vec<usize>, },
-    Move { num: usize, from: usize, to: usize, }
+struct Move {
+    num: usize,
+    from: usize,
+    to: usize,
 }
 
 struct CrateStacks {
@@ -47,28 +53,41 @@ impl CrateStacks {
     }
 }
 
-fn parse_line(line: String) -> Line {
-    match line.chars().next() {
-        Some('m') => parse_move_line(&line),
-        Some(' ') => parse_nums_line(&line),
-        Some(_) => parse_crates_line(&line),
-        None => Line::Empty,
+///The drawing has the crates, from the top down, and finally the numbers of the stacks
+fn parse_drawing(drawing: &Record) -> Result<CrateStacks, Error> {
+    let (nums_line, crates_lines) = drawing.lines().split_last()
+        .ok_or_else(|| drawing.error("expected a drawing of the stacks"))?;
+    let mut crate_stacks = CrateStacks::new();
+    for crates_line in crates_lines.iter().rev() {
+        for (row, crate_id) in parse_crates_line(crates_line) {
+            crate_stacks.stacks.entry(row).or_default().push(crate_id);
+        }
     }
+    let nums = parse_nums_line(nums_line);
+    if nums.is_empty() {
+        return Err(drawing.error("expected the drawing to end with the numbers of the stacks"));
+    }
+    let mut numbered_stacks = CrateStacks::new();
+    for (index, row) in nums.iter().enumerate() {
+        let stack = crate_stacks.stacks.remove(&index).unwrap_or_default();
+        numbered_stacks.stacks.insert(*row, stack);
+    }
+    Ok(numbered_stacks)
 }
 
-fn parse_move_line(line: &String) -> Line {
+fn parse_move_line(line: &str) -> ParseResult<Move> {
     lazy_static! {
         static ref MOVE: Regex = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
     }
-    let caps = MOVE.captures(line).unwrap();
-    Line::Move {
-        num: caps.get(1).unwrap().as_str().parse().unwrap(),
-        from: caps.get(2).unwrap().as_str().parse().unwrap(),
-        to: caps.get(3).unwrap().as_str().parse().unwrap(),
-    }
+    let caps = MOVE.captures(line).ok_or("expected move <num> from <stack> to <stack>")?;
+    Ok(Move {
+        num: caps[1].parse()?,
+        from: caps[2].parse()?,
+        to: caps[3].parse()?,
+    })
 }
 
-fn parse_nums_line(line: &String) -> Line {
+fn parse_nums_line(line: &str) -> Vec<usize> {
     let mut chars = line.chars();
     let mut rows: Vec<usize> = Vec::new();
     loop {
@@ -83,10 +102,10 @@ fn parse_nums_line(line: &String) -> Line {
             break;
         }
     }
-    Line::Rows { rows }
+    rows
 }
 
-fn parse_crates_line(line: &String) -> Line {
+fn parse_crates_line(line: &str) -> HashMap<usize, char> {
     let mut chars = line.chars();
     let mut crates: HashMap<usize, char> = HashMap::new();
     let mut row_index: usize = 0;
@@ -96,11 +115,11 @@ fn parse_crates_line(line: &String) -> Line {
         let _space2 = chars.next();
         match crate_id {
             Some(' ') => {
-                row_index = row_index + 1
+                row_index += 1
             },
             Some(id) => {
                 crates.insert(row_index, id);
-                row_index = row_index + 1;
+                row_index += 1;
             },
             None => break,
         }
@@ -108,11 +127,7 @@ fn parse_crates_line(line: &String) -> Line {
             break;
         }
     }
-    Line::Crates { crates_by_row: crates }
-}
-
-fn accumulate1(crate_stacks: CrateStacks, line: Line) -> CrateStacks {
-    accumulate_gen(crate_stacks, line, move1)
+    crates
 }
 
 fn move1(mut crate_stacks: CrateStacks, num: usize, from: usize, to: usize) -> CrateStacks {
@@ -124,10 +139,6 @@ fn move1(mut crate_stacks: CrateStacks, num: usize, from: usize, to: usize) -> C
     crate_stacks
 }
 
-fn accumulate2(crate_stacks: CrateStacks, line: Line) -> CrateStacks {
-    accumulate_gen(crate_stacks, line, move2)
-}
-
 fn move2(mut crate_stacks: CrateStacks, num: usize, from: usize, to: usize) -> CrateStacks {
     let mut items = {
         let from_stack = crate_stacks.stacks.get_mut(&from).unwrap();
@@ -137,41 +148,6 @@ fn move2(mut crate_stacks: CrateStacks, num: usize, from: usize, to: usize) -> C
     crate_stacks
 }
 
-fn accumulate_gen(mut crate_stacks: CrateStacks,
-                  line: Line,
-                  move_func: fn (CrateStacks, usize, usize, usize) -> CrateStacks) -> CrateStacks {
-    match line {
-        Line::Empty => {
-            for (_, stack) in crate_stacks.stacks.iter_mut() {
-                stack.reverse()
-            };
-            crate_stacks
-        },
-        Line::Crates { crates_by_row } => {
-            for (row, crate_id) in crates_by_row.iter() {
-                match crate_stacks.stacks.get_mut(&row) {
-                    Some(stack) => stack.push(*crate_id),
-                    None => {
-                        let mut new_stack = Vec::new();
-                        new_stack.push(*crate_id);
-                        crate_stacks.stacks.insert(*row, new_stack);
-                    },
-                };
-            };
-            crate_stacks
-        },
-        Line::Move { num, from, to } => move_func(crate_stacks, num, from, to),
-        Line::Rows { rows } => {
-            let mut new_stacks = CrateStacks::new();
-            for (index, row) in rows.iter().enumerate() {
-                let stack = crate_stacks.stacks.remove(&index).unwrap();
-                new_stacks.stacks.insert(*row, stack);
-            }
-            new_stacks
-        }
-    }
-}
-
 fn reduce(crate_stacks: CrateStacks) -> String {
     crate_stacks.stacks.values().map(|stack| {
         match stack.last() {
@@ -1,14 +1,13 @@
 use lazy_static::lazy_static;
 use regex::Regex;
 
+use crate::error::{Error, ParseResult};
 use crate::utils;
-use std::{io::Error, convert::identity};
+use std::convert::identity;
 
-
-pub fn _4a() -> Result<u64, Error> {
-    let filename = "4/input.txt";
-    utils::process_file(
-        filename,
+pub fn _4a(input: utils::Input) -> Result<u64, Error> {
+    utils::try_process_file(
+        input,
         parse_line,
         0,
         accumulate1,
@@ -16,10 +15,9 @@ pub fn _4a() -> Result<u64, Error> {
     )
 }
 
-pub fn _4b() -> Result<u64, Error> {
-    let filename = "4/input.txt";
-    utils::process_file(
-        filename,
+pub fn _4b(input: utils::Input) -> Result<u64, Error> {
+    utils::try_process_file(
+        input,
         parse_line,
         0,
         accumulate2,
@@ -39,24 +37,18 @@ impl Range {
     }
 }
 
-fn parse_line(line: String) -> (Range, Range) {
+fn parse_line(line: &str) -> ParseResult<(Range, Range)> {
     lazy_static! {
         static ref RE: Regex = Regex::new(r"^(\d+)-(\d+),(\d+)-(\d+)$").unwrap();
     }
-    match RE.captures(&line) {
+    match RE.captures(line) {
         Some(caps) => {
-            (
-                Range::new(
-                    caps.get(1).unwrap().as_str().parse().unwrap(),
-                    caps.get(2).unwrap().as_str().parse().unwrap(),
-                ),
-                Range::new(
-                    caps.get(3).unwrap().as_str().parse().unwrap(),
-                    caps.get(4).unwrap().as_str().parse().unwrap(),
-                ),
-            )
+            Ok((
+                Range::new(caps[1].parse()?, caps[2].parse()?),
+                Range::new(caps[3].parse()?, caps[4].parse()?),
+            ))
         },
-        None => panic!("Line didn't match expected: {}", line),
+        None => Err("expected two ranges, e.g. 2-4,6-8".into()),
     }
 }
 
@@ -1,21 +1,19 @@
 use std::cmp::{max, min};
-use std::collections::HashMap;
 use std::fmt::Display;
 use std::fs::File;
 use std::io::{BufWriter, Write};
 
-use lazy_static::lazy_static;
-
+use crate::direction::Direction8;
+use crate::error::{Error, ParseResult};
+use crate::grid::{Grid, Pos, Scale, SparseGrid};
 use crate::utils;
 
-const FILE_NAME: &str = "14/input.txt";
-
-pub fn _14a() -> Result<u32, std::io::Error> {
-    utils::process_file(FILE_NAME, parse_line, State::new(), accumulate, reduce1)
+pub fn _14a(input: utils::Input) -> Result<u32, Error> {
+    utils::try_process_file(input, parse_line, State::new(), accumulate, reduce1)
 }
 
-pub fn _14b() -> Result<u32, std::io::Error> {
-    utils::process_file(FILE_NAME, parse_line, State::new(), accumulate, reduce2)
+pub fn _14b(input: utils::Input) -> Result<u32, Error> {
+    utils::try_process_file(input, parse_line, State::new(), accumulate, reduce2)
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -36,19 +34,19 @@ impl Display for Link {
     }
 }
 
-fn parse_line(line: String) -> Vec<Link> {
+fn parse_line(line: &str) -> ParseResult<Vec<Link>> {
     let mut links = Vec::with_capacity(10);
     let mut x_opt: Option<Scale> = None;
     let mut num = String::new();
     for c in line.chars() {
         match c {
             ',' => {
-                x_opt = Some(num.parse().unwrap());
+                x_opt = Some(num.parse()?);
                 num.clear()
             }
             ' ' => {
                 if let Some(x) = x_opt {
-                    let y: Scale = num.parse().unwrap();
+                    let y: Scale = num.parse()?;
                     num.clear();
                     links.push(Link::new(x, y));
                     x_opt = None
@@ -60,24 +58,10 @@ fn parse_line(line: String) -> Vec<Link> {
     }
     //final link needs to be created
     if let Some(x) = x_opt {
-        let y = num.parse().unwrap();
+        let y = num.parse()?;
         links.push(Link::new(x, y));
     }
-    links
-}
-
-type Scale = i32;
-
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct Pos {
-    x: Scale,
-    y: Scale,
-}
-
-impl Pos {
-    pub fn new(x: Scale, y: Scale) -> Pos {
-        Pos { x, y }
-    }
+    Ok(links)
 }
 
 enum Blockage {
@@ -85,14 +69,23 @@ enum Blockage {
     Sand,
 }
 
+impl Display for Blockage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Blockage::Rock => write!(f, "#"),
+            Blockage::Sand => write!(f, "o"),
+        }
+    }
+}
+
 struct State {
-    blockages: HashMap<Pos, Blockage>,
+    blockages: SparseGrid<Blockage>,
 }
 
 impl State {
     pub fn new() -> State {
         State {
-            blockages: HashMap::new(),
+            blockages: SparseGrid::new(),
         }
     }
 }
@@ -100,52 +93,21 @@ impl State {
 // fn output_links(links: &Vec<Link>) {
 //     let mut link_iter = links.iter();
 //     if let Some(link) = link_iter.next() {
-//         print!("{}", link);
+//         eprint!("{}", link);
 //     }
 //     for link in link_iter {
-//         print!(" -> {}", link);
+//         eprint!(" -> {}", link);
 //     }
-//     println!("");
+//     eprintln!("");
 // }
 
-///returns the bounds of the rocks (min_x, min_y, max_x, max_y)
-fn calc_bounds(state: &State) -> (Scale, Scale, Scale, Scale) {
-    let mut min_x = Scale::MAX;
-    let mut min_y = Scale::MAX;
-    let mut max_x = Scale::MIN;
-    let mut max_y = Scale::MIN;
-    for pos in state.blockages.keys() {
-        min_x = min(pos.x, min_x);
-        min_y = min(pos.y, min_y);
-        max_x = max(pos.x, max_x);
-        max_y = max(pos.y, max_y);
-    }
-    (min_x, min_y, max_x, max_y)
-}
-
 fn output_state(state: &State, file_name: &str) {
-    lazy_static! {
-        static ref BLOCK: String = String::from("#");
-        static ref SAND: String = String::from("o");
-        static ref EMPTY: String = String::from(".");
-    }
     File::create(file_name)
         .map(BufWriter::new)
         .map(|mut writer| {
-            let (min_x, min_y, max_x, max_y) = calc_bounds(state);
-            writeln!(writer, "{}-{}x{}-{}", min_x, max_x, min_y, max_y).unwrap();
-            for y in min_y..(max_y + 1) {
-                for x in min_x..(max_x + 1) {
-                    let pos = Pos::new(x, y);
-                    match state.blockages.get(&pos) {
-                        Some(Blockage::Rock) => writer.write(BLOCK.as_bytes()),
-                        Some(Blockage::Sand) => writer.write(SAND.as_bytes()),
-                        None => writer.write(EMPTY.as_bytes()),
-                    }
-                    .unwrap();
-                }
-                writeln!(writer).unwrap();
-            }
+            let bounds = state.blockages.bounds();
+            writeln!(writer, "{}-{}x{}-{}", bounds.min_x, bounds.max_x, bounds.min_y, bounds.max_y).unwrap();
+            write!(writer, "{}", state.blockages).unwrap();
             writer.flush().unwrap();
         })
         .unwrap();
@@ -186,15 +148,12 @@ fn start_pos() -> Pos {
 fn reduce1(mut state: State) -> u32 {
     // output_state(&state, "14/reduce1-start-output.txt");
 
-    let (_, _, _, escape_y) = calc_bounds(&state);
+    let escape_y = state.blockages.bounds().max_y;
 
     let mut pos = start_pos();
     let mut grains_at_rest: u32 = 0;
     loop {
-        if !move_down(&state, &mut pos)
-            && !move_down_left(&state, &mut pos)
-            && !move_down_right(&state, &mut pos)
-        {
+        if !fall(&state, &mut pos) {
             //unable to move - new grain at rest and add a blockage
             grains_at_rest += 1;
             state.blockages.insert(pos, Blockage::Sand);
@@ -214,7 +173,7 @@ fn reduce1(mut state: State) -> u32 {
 fn reduce2(mut state: State) -> u32 {
     // output_state(&state);
 
-    let (_, _, _, lowest_rock) = calc_bounds(&state);
+    let lowest_rock = state.blockages.bounds().max_y;
 
     let lowest_y = lowest_rock + 1;
     let mut pos = start_pos();
@@ -228,10 +187,7 @@ fn reduce2(mut state: State) -> u32 {
             continue;
         }
 
-        if !move_down(&state, &mut pos)
-            && !move_down_left(&state, &mut pos)
-            && !move_down_right(&state, &mut pos)
-        {
+        if !fall(&state, &mut pos) {
             //unable to move - new grain at rest and add a blockage
             grains_at_rest += 1;
             state.blockages.insert(pos, Blockage::Sand);
@@ -243,9 +199,8 @@ fn reduce2(mut state: State) -> u32 {
         }
     }
 
-    let (min_x, _, max_x, _) = calc_bounds(&state);
     //draw the floor
-    for x in min_x..(max_x + 1) {
+    for x in state.blockages.bounds().columns() {
         state
             .blockages
             .insert(Pos::new(x, lowest_rock + 2), Blockage::Rock);
@@ -255,45 +210,17 @@ fn reduce2(mut state: State) -> u32 {
     grains_at_rest
 }
 
-fn move_down(state: &State, pos: &mut Pos) -> bool {
-    //see if we can move down
-    pos.y += 1;
-    match state.blockages.get(pos) {
-        Some(_) => {
-            //blocked - put it back
-            pos.y -= 1;
-            false
-        }
-        None => true, //ok, it's moved
-    }
-}
-
-fn move_down_left(state: &State, pos: &mut Pos) -> bool {
-    //see if we can move down and left
-    pos.x -= 1;
-    pos.y += 1;
-    match state.blockages.get(pos) {
-        Some(_) => {
-            //blocked - put it back
-            pos.x += 1;
-            pos.y -= 1;
-            false
-        }
-        None => true, //ok, it's moved
-    }
-}
+///Where a grain of sand tries to fall, in order
+const FALLS: [Direction8; 3] = [Direction8::South, Direction8::SouthWest, Direction8::SouthEast];
 
-fn move_down_right(state: &State, pos: &mut Pos) -> bool {
-    //see if we can move down and right
-    pos.x += 1;
-    pos.y += 1;
-    match state.blockages.get(pos) {
-        Some(_) => {
-            //blocked - put it back
-            pos.x -= 1;
-            pos.y -= 1;
-            false
+///Move the grain down a step if it can, returning false if it has come to rest
+fn fall(state: &State, pos: &mut Pos) -> bool {
+    for direction in FALLS {
+        let next = *pos + direction.offset();
+        if !state.blockages.contains(next) {
+            *pos = next;
+            return true;
         }
-        None => true, //ok, it's moved
     }
+    false
 }
@@ -0,0 +1,36 @@
+//The 2022 solutions. Each day's module has its part a and b solvers (_1a, _1b, ...) which read a
+//utils::Input, either a file or the text of the input itself, along with anything of the day's that
+//is useful elsewhere (e.g. thirteen::Item, twentyfive::to_snafu). The runner module has every
+//solver for the top level runner. Solvers print their progress to stderr, stdout is left for the
+//runner's report of the answers.
+
+pub use aoc_common::{direction, error, grid, optimise, point, search, utils};
+
+pub mod eight;
+pub mod eighteen;
+pub mod eleven;
+pub mod fifteen;
+pub mod five;
+pub mod four;
+pub mod fourteen;
+pub mod nine;
+pub mod nineteen;
+pub mod one;
+#[cfg(test)]
+mod regression;
+pub mod runner;
+pub mod seven;
+pub mod seventeen;
+pub mod six;
+pub mod sixteen;
+pub mod ten;
+pub mod thirteen;
+pub mod three;
+pub mod twelve;
+pub mod twenty;
+pub mod twentyone;
+pub mod twentytwo;
+pub mod twentythree;
+pub mod twentyfour;
+pub mod twentyfive;
+pub mod two;
@@ -1,17 +1,21 @@
-use std::cmp::Ordering;
 use std::fmt::Display;
+use std::thread;
 
+use crate::error::{Error, ParseResult};
+use crate::optimise;
 use crate::utils;
 
-const FILE_NAME: &str = "19/input.txt";
-//const FILE_NAME: &str = "19/test_input.txt";
+pub fn _19a(input: utils::Input) -> Result<usize, Error> {
+    utils::try_process_file(input, parse_line, State::new(), accumulate, reduce1)
+}
 
-pub fn _19a() -> Result<usize, std::io::Error> {
-    utils::process_file(FILE_NAME, parse_line, State::new(), accumulate, reduce1)
+pub fn _19b(input: utils::Input) -> Result<usize, Error> {
+    utils::try_process_file(input, parse_line, State::new(), accumulate, reduce2)
 }
 
-pub fn _19b() -> Result<usize, std::io::Error> {
-    utils::process_file(FILE_NAME, parse_line, State::new(), accumulate, reduce2)
+///Read the costs in each blueprint, for benchmarking [best_builds]
+pub fn read_blueprints(input: utils::Input) -> Result<Vec<Costs>, Error> {
+    utils::try_process_file(input, parse_line, State::new(), accumulate, |state| state.costs_to_check)
 }
 
 const BLUEPRINT: &str = "Blueprint ";
@@ -22,7 +26,7 @@ const OBSIDIAN_ROBOT_CLAY: &str = "ore and ";
 const GEODE_ROBOT_ORE: &str = "clay. Each geode robot costs ";
 const GEODE_ROBOT_OBSIDIAN: &str = "ore and ";
 
-struct Costs {
+pub struct Costs {
     blueprint_id: usize,
     ore_robot_ore_cost: usize,
     clay_robot_ore_cost: usize,
@@ -52,6 +56,16 @@ impl Costs {
             geode_robot_obsidian_cost,
         }
     }
+
+    ///The ore, clay and obsidian it takes to build a robot
+    fn of(&self, robot: Robot) -> (usize, usize, usize) {
+        match robot {
+            Robot::OreCollecting => (self.ore_robot_ore_cost, 0, 0),
+            Robot::ClayCollecting => (self.clay_robot_ore_cost, 0, 0),
+            Robot::ObsidianCollecting => (self.obsidian_robot_ore_cost, self.obsidian_robot_clay_cost, 0),
+            Robot::GeodeCracking => (self.geode_robot_ore_cost, 0, self.geode_robot_obsidian_cost),
+        }
+    }
 }
 
 impl Display for Costs {
@@ -77,24 +91,28 @@ impl Display for Costs {
     }
 }
 
-fn parse_line(line: String) -> Costs {
+fn parse_line(line: &str) -> ParseResult<Costs> {
     //Blueprint 1: Each ore robot costs 2 ore. Each clay robot costs 4 ore. Each obsidian robot costs 4 ore and 20 clay. Each geode robot costs 3 ore and 14 obsidian.
-    let mut chars = line.chars();
-    utils::skip(&mut chars, BLUEPRINT.len());
-    let blueprint_id: usize = utils::parse_next_number(&mut chars).unwrap();
-    utils::skip(&mut chars, ORE_ROBOT.len());
-    let ore_robot_ore_cost: usize = utils::parse_next_number(&mut chars).unwrap();
-    utils::skip(&mut chars, CLAY_ROBOT.len());
-    let clay_robot_ore_cost: usize = utils::parse_next_number(&mut chars).unwrap();
-    utils::skip(&mut chars, OBSIDIAN_ROBOT_ORE.len());
-    let obsidian_robot_ore_cost: usize = utils::parse_next_number(&mut chars).unwrap();
-    utils::skip(&mut chars, OBSIDIAN_ROBOT_CLAY.len());
-    let obsidian_robot_clay_cost: usize = utils::parse_next_number(&mut chars).unwrap();
-    utils::skip(&mut chars, GEODE_ROBOT_ORE.len());
-    let geode_robot_ore_cost: usize = utils::parse_next_number(&mut chars).unwrap();
-    utils::skip(&mut chars, GEODE_ROBOT_OBSIDIAN.len());
-    let geode_robot_obsidian_cost: usize = utils::parse_next_number(&mut chars).unwrap();
-    Costs::new(
+    let mut scanner = utils::Scanner::new(line);
+    scanner.expect_literal(BLUEPRINT)?;
+    let blueprint_id: usize = scanner.next_int()?;
+    scanner.expect_literal(ORE_ROBOT)?;
+    let ore_robot_ore_cost: usize = scanner.next_int()?;
+    scanner.expect_literal(CLAY_ROBOT)?;
+    let clay_robot_ore_cost: usize = scanner.next_int()?;
+    scanner.expect_literal(OBSIDIAN_ROBOT_ORE)?;
+    let obsidian_robot_ore_cost: usize = scanner.next_int()?;
+    scanner.expect_literal(OBSIDIAN_ROBOT_CLAY)?;
+    let obsidian_robot_clay_cost: usize = scanner.next_int()?;
+    scanner.expect_literal(GEODE_ROBOT_ORE)?;
+    let geode_robot_ore_cost: usize = scanner.next_int()?;
+    scanner.expect_literal(GEODE_ROBOT_OBSIDIAN)?;
+    let geode_robot_obsidian_cost: usize = scanner.next_int()?;
+    scanner.expect_literal("obsidian.")?;
+    if !scanner.is_at_end() {
+        return Err(format!("unexpected '{}' after the costs", scanner.rest().trim()).into());
+    }
+    Ok(Costs::new(
         blueprint_id,
         ore_robot_ore_cost,
         clay_robot_ore_cost,
@@ -102,7 +120,7 @@ fn parse_line(line: String) -> Costs {
         obsidian_robot_clay_cost,
         geode_robot_ore_cost,
         geode_robot_obsidian_cost,
-    )
+    ))
 }
 
 struct State {
@@ -118,33 +136,101 @@ impl State {
 }
 
 fn accumulate(mut state: State, costs: Costs) -> State {
-    println!("{}", costs);
+    eprintln!("{}", costs);
     state.costs_to_check.push(costs);
     state
 }
 
 fn reduce1(state: State) -> usize {
     let mut total_quality_level = 0;
-    for costs in state.costs_to_check {
-        let quality = run_simulation(&costs, 24);
-        println!("Blueprint {}: Quality: {}", costs.blueprint_id, quality);
-        total_quality_level += costs.blueprint_id * quality;
+    for builds in best_builds_in_parallel(&state.costs_to_check, 24) {
+        eprintln!("{}", builds);
+        total_quality_level += builds.blueprint_id * builds.geodes;
     }
     total_quality_level
 }
 
 fn reduce2(state: State) -> usize {
     let mut total = 1;
-    for i in 0..3 {
-        let costs = state.costs_to_check.get(i).unwrap();
-        let quality = run_simulation(costs, 32);
-        println!("Blueprint {}: Quality: {}", costs.blueprint_id, quality);
-        total *= quality;
+    for builds in best_builds_in_parallel(state.costs_to_check.iter().take(3), 32) {
+        eprintln!("{}", builds);
+        total *= builds.geodes;
     }
     total
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Robot {
+    OreCollecting,
+    ClayCollecting,
+    ObsidianCollecting,
+    GeodeCracking,
+}
+
+impl Robot {
+    pub const ALL: [Robot; 4] = [
+        Robot::OreCollecting,
+        Robot::ClayCollecting,
+        Robot::ObsidianCollecting,
+        Robot::GeodeCracking,
+    ];
+}
+
+impl Display for Robot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Robot::OreCollecting => "ore-collecting",
+            Robot::ClayCollecting => "clay-collecting",
+            Robot::ObsidianCollecting => "obsidian-collecting",
+            Robot::GeodeCracking => "geode-cracking",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+///The most geodes that a blueprint can open, and the robots to build to open them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Builds {
+    pub blueprint_id: usize,
+    pub geodes: usize,
+    ///Each robot built, in order, with the minute (from 1) it is built in
+    pub order: Vec<(usize, Robot)>,
+}
+
+impl Display for Builds {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Blueprint {}: {} geodes building", self.blueprint_id, self.geodes)?;
+        for (minute, robot) in self.order.iter() {
+            write!(f, " {}@{}", robot, minute)?;
+        }
+        Ok(())
+    }
+}
+
+///The best builds for each blueprint, each worked out on a thread of its own
+pub fn best_builds_in_parallel<'a>(blueprints: impl IntoIterator<Item = &'a Costs>, max_steps: usize) -> Vec<Builds> {
+    thread::scope(|scope| {
+        let threads: Vec<_> = blueprints
+            .into_iter()
+            .map(|costs| scope.spawn(move || best_builds(costs, max_steps)))
+            .collect();
+        threads.into_iter().map(|thread| thread.join().unwrap()).collect()
+    })
+}
+
+///The most geodes that can be opened with the blueprint in max_steps minutes
+pub fn best_builds(costs: &Costs, max_steps: usize) -> Builds {
+    let best = optimise::branch_and_bound(&Factory::new(costs, max_steps), Step::new());
+    Builds {
+        blueprint_id: costs.blueprint_id,
+        geodes: best.score,
+        order: best.state.built,
+    }
+}
+
+#[derive(Clone)]
 struct Step {
+    ///Minutes gone
     minute: usize,
 
     ore_collecting_robots: usize,
@@ -156,12 +242,14 @@ struct Step {
     clay: usize,
     obsidian: usize,
     geodes: usize,
+
+    built: Vec<(usize, Robot)>,
 }
 
 impl Step {
     pub fn new() -> Step {
         Step {
-            minute: 1,
+            minute: 0,
 
             ore_collecting_robots: 1,
             clay_collecting_robots: 0,
@@ -172,167 +260,123 @@ impl Step {
             clay: 0,
             obsidian: 0,
             geodes: 0,
+
+            built: Vec::new(),
         }
     }
-}
-
-const MAX_TO_KEEP: usize = 200;
-
-fn run_simulation(costs: &Costs, max_steps: usize) -> usize {
-    let mut max_opened_geodes: usize = 0;
-
-    let this_run_steps: &mut Vec<Step> = &mut Vec::new();
-    this_run_steps.push(Step::new());
-    let next_run_steps: &mut Vec<Step> = &mut Vec::new();
 
-    while !this_run_steps.is_empty() {
-        let mut minute = 0;
-        'inner: for step in this_run_steps.iter() {
-            minute = step.minute;
-            if step.minute >= max_steps {
-                //make final resource collections
-                let final_step = build_nothing(&step);
-                max_opened_geodes = std::cmp::max(max_opened_geodes, final_step.geodes);
-                continue 'inner;
-            }
-
-            if let Some(new_step) = build_ore_collecting_robot(costs, &step) {
-                next_run_steps.push(new_step);
-            }
-            if let Some(new_step) = build_clay_collecting_robot(costs, &step) {
-                next_run_steps.push(new_step);
-            }
-            if let Some(new_step) = build_obsidian_collecting_robot(costs, &step) {
-                next_run_steps.push(new_step);
-            }
-            if let Some(new_step) = build_geode_cracking_robot(costs, &step) {
-                next_run_steps.push(new_step);
-            }
-            //wait for more collection
-            let new_step = build_nothing(&step);
-            next_run_steps.push(new_step);
-        }
+    fn collect(&mut self, minutes: usize) {
+        self.minute += minutes;
+        self.ore += self.ore_collecting_robots * minutes;
+        self.clay += self.clay_collecting_robots * minutes;
+        self.obsidian += self.obsidian_collecting_robots * minutes;
+        self.geodes += self.geode_cracking_robots * minutes;
+    }
 
-        this_run_steps.clear();
-        //limit number of next run steps
-        if next_run_steps.len() > MAX_TO_KEEP {
-            print!(
-                "Min {}: {} -> {},",
-                minute,
-                next_run_steps.len(),
-                MAX_TO_KEEP
-            );
-            next_run_steps.sort_by(sort_steps);
-            next_run_steps.truncate(MAX_TO_KEEP);
+    fn robots_mut(&mut self, robot: Robot) -> &mut usize {
+        match robot {
+            Robot::OreCollecting => &mut self.ore_collecting_robots,
+            Robot::ClayCollecting => &mut self.clay_collecting_robots,
+            Robot::ObsidianCollecting => &mut self.obsidian_collecting_robots,
+            Robot::GeodeCracking => &mut self.geode_cracking_robots,
         }
-        //swap pointers
-        std::mem::swap(this_run_steps, next_run_steps);
     }
-    println!("");
-
-    max_opened_geodes
 }
 
-//Prefer geodes over obsidian over clay over ore
-//Prefer robots over resources
-fn sort_steps(s1: &Step, s2: &Step) -> Ordering {
-    Ordering::Equal
-        .then_with(|| s2.geode_cracking_robots.cmp(&s1.geode_cracking_robots))
-        .then_with(|| s2.geodes.cmp(&s1.geodes))
-        .then_with(|| s2.obsidian_collecting_robots.cmp(&s1.obsidian_collecting_robots))
-        .then_with(|| s2.obsidian.cmp(&s1.obsidian))
-        .then_with(|| s2.clay_collecting_robots.cmp(&s1.clay_collecting_robots))
-        .then_with(|| s2.clay.cmp(&s1.clay))
-        .then_with(|| s2.ore_collecting_robots.cmp(&s1.ore_collecting_robots))
-        .then_with(|| s2.ore.cmp(&s1.ore))
-}
-
-fn build_ore_collecting_robot(costs: &Costs, step: &Step) -> Option<Step> {
-    if costs.ore_robot_ore_cost <= step.ore {
-        Some(Step {
-            minute: step.minute + 1,
-
-            ore: step.ore - costs.ore_robot_ore_cost + step.ore_collecting_robots,
-            clay: step.clay + step.clay_collecting_robots,
-            obsidian: step.obsidian + step.obsidian_collecting_robots,
-            geodes: step.geodes + step.geode_cracking_robots,
-
-            ore_collecting_robots: step.ore_collecting_robots + 1,
-
-            ..*step
-        })
-    } else {
+///The minutes until robots have collected needed when there is already have, None if they never will
+fn minutes_to_collect(needed: usize, have: usize, robots: usize) -> Option<usize> {
+    if have >= needed {
+        Some(0)
+    } else if robots == 0 {
         None
-    }
-}
-
-fn build_clay_collecting_robot(costs: &Costs, step: &Step) -> Option<Step> {
-    if costs.clay_robot_ore_cost <= step.ore {
-        Some(Step {
-            minute: step.minute + 1,
-
-            ore: step.ore - costs.clay_robot_ore_cost + step.ore_collecting_robots,
-            clay: step.clay + step.clay_collecting_robots,
-            obsidian: step.obsidian + step.obsidian_collecting_robots,
-            geodes: step.geodes + step.geode_cracking_robots,
-
-            clay_collecting_robots: step.clay_collecting_robots + 1,
-
-            ..*step
-        })
     } else {
-        None
+        Some((needed - have).div_ceil(robots))
     }
 }
 
-fn build_obsidian_collecting_robot(costs: &Costs, step: &Step) -> Option<Step> {
-    if costs.obsidian_robot_ore_cost <= step.ore && costs.obsidian_robot_clay_cost <= step.clay {
-        Some(Step {
-            minute: step.minute + 1,
+///Building robots with a blueprint for max_steps minutes, as a problem for the optimiser. Rather
+/// than choosing what to do each minute, the choice is which robot to build next.
+struct Factory<'a> {
+    costs: &'a Costs,
+    max_steps: usize,
+    ///Only so much of each resource can be spent in a minute, so there's no use collecting more
+    max_ore_collecting_robots: usize,
+}
 
-            ore: step.ore - costs.obsidian_robot_ore_cost + step.ore_collecting_robots,
-            clay: step.clay - costs.obsidian_robot_clay_cost + step.clay_collecting_robots,
-            obsidian: step.obsidian + step.obsidian_collecting_robots,
-            geodes: step.geodes + step.geode_cracking_robots,
+impl Factory<'_> {
+    fn new(costs: &Costs, max_steps: usize) -> Factory<'_> {
+        let max_ore_collecting_robots = Robot::ALL.iter().map(|robot| costs.of(*robot).0).max().unwrap();
+        Factory {
+            costs,
+            max_steps,
+            max_ore_collecting_robots,
+        }
+    }
 
-            obsidian_collecting_robots: step.obsidian_collecting_robots + 1,
+    fn worth_building(&self, step: &Step, robot: Robot) -> bool {
+        match robot {
+            Robot::OreCollecting => step.ore_collecting_robots < self.max_ore_collecting_robots,
+            Robot::ClayCollecting => step.clay_collecting_robots < self.costs.obsidian_robot_clay_cost,
+            Robot::ObsidianCollecting => step.obsidian_collecting_robots < self.costs.geode_robot_obsidian_cost,
+            Robot::GeodeCracking => true,
+        }
+    }
 
-            ..*step
-        })
-    } else {
-        None
+    ///Wait until the robot can be built and then build it, None if it can't be built in time to be
+    /// of use
+    fn build(&self, step: &Step, robot: Robot) -> Option<Step> {
+        let (ore, clay, obsidian) = self.costs.of(robot);
+        let wait = minutes_to_collect(ore, step.ore, step.ore_collecting_robots)?
+            .max(minutes_to_collect(clay, step.clay, step.clay_collecting_robots)?)
+            .max(minutes_to_collect(obsidian, step.obsidian, step.obsidian_collecting_robots)?);
+        //Building takes a minute, and there's no point building in the last one
+        if step.minute + wait + 1 >= self.max_steps {
+            return None;
+        }
+        let mut next_step = step.clone();
+        next_step.collect(wait + 1);
+        next_step.ore -= ore;
+        next_step.clay -= clay;
+        next_step.obsidian -= obsidian;
+        *next_step.robots_mut(robot) += 1;
+        next_step.built.push((next_step.minute, robot));
+        Some(next_step)
     }
 }
 
-fn build_geode_cracking_robot(costs: &Costs, step: &Step) -> Option<Step> {
-    if costs.geode_robot_ore_cost <= step.ore && costs.geode_robot_obsidian_cost <= step.obsidian {
-        Some(Step {
-            minute: step.minute + 1,
-
-            ore: step.ore - costs.geode_robot_ore_cost + step.ore_collecting_robots,
-            clay: step.clay + step.clay_collecting_robots,
-            obsidian: step.obsidian - costs.geode_robot_obsidian_cost
-                + step.obsidian_collecting_robots,
-            geodes: step.geodes + step.geode_cracking_robots,
+impl optimise::Problem for Factory<'_> {
+    type State = Step;
+    type Score = usize;
 
-            geode_cracking_robots: step.geode_cracking_robots + 1,
-
-            ..*step
-        })
-    } else {
-        None
+    fn successors(&self, step: &Step) -> Vec<Step> {
+        Robot::ALL
+            .into_iter()
+            .filter(|robot| self.worth_building(step, *robot))
+            .filter_map(|robot| self.build(step, robot))
+            .collect()
     }
-}
 
-fn build_nothing(step: &Step) -> Step {
-    Step {
-        minute: step.minute + 1,
-
-        ore: step.ore + step.ore_collecting_robots,
-        clay: step.clay + step.clay_collecting_robots,
-        obsidian: step.obsidian + step.obsidian_collecting_robots,
-        geodes: step.geodes + step.geode_cracking_robots,
+    ///The geodes opened by the end if no more robots are built
+    fn score(&self, step: &Step) -> usize {
+        step.geodes + step.geode_cracking_robots * (self.max_steps - step.minute)
+    }
 
-        ..*step
+    ///As if ore and clay were free, and an obsidian collecting robot could be built every minute as
+    /// well as a geode cracking robot whenever there's the obsidian
+    fn bound(&self, step: &Step) -> usize {
+        let mut obsidian = step.obsidian;
+        let mut geodes = step.geodes;
+        let mut geode_cracking_robots = step.geode_cracking_robots;
+        let minutes_left = self.max_steps - step.minute;
+        for obsidian_collecting_robots in (step.obsidian_collecting_robots..).take(minutes_left) {
+            let build_geode_cracking_robot = obsidian >= self.costs.geode_robot_obsidian_cost;
+            obsidian += obsidian_collecting_robots;
+            geodes += geode_cracking_robots;
+            if build_geode_cracking_robot {
+                obsidian -= self.costs.geode_robot_obsidian_cost;
+                geode_cracking_robots += 1;
+            }
+        }
+        geodes
     }
 }
@@ -0,0 +1,141 @@
+//Runs every solution against the answers recorded in expected_answers.txt, one test per day,
+//so that refactoring shared code can't quietly change an answer.
+
+use std::fs;
+use std::panic;
+use std::path::Path;
+
+use aoc_common::runner::{Day, Solver};
+use aoc_common::scaffold;
+
+use crate::fifteen;
+use crate::runner;
+use crate::utils::Input;
+
+const MANIFEST: &str = runner::YEAR.layout.expected_answers;
+
+struct Expected {
+    line_number: usize,
+    day: Day,
+    part: String,
+    input: String,
+    answer: String,
+}
+
+///Read the manifest, ignoring blank lines and # comments
+fn read_manifest() -> Vec<Expected> {
+    let contents = fs::read_to_string(MANIFEST)
+        .unwrap_or_else(|err| panic!("Could not read {}: {}", MANIFEST, err));
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(index, line)| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields[..] {
+                [day, part, input, answer] => Expected {
+                    line_number: index + 1,
+                    day: day.parse().unwrap_or_else(|_| panic!("{}:{} bad day: {}", MANIFEST, index + 1, line)),
+                    part: String::from(part),
+                    input: String::from(input),
+                    answer: String::from(answer),
+                },
+                _ => panic!("{}:{} expected '<day> <part> <input file> <answer>': {}", MANIFEST, index + 1, line),
+            }
+        })
+        .collect()
+}
+
+///Run the day's entries from the manifest, returning a line describing each one that didn't match
+fn check_day(day: Day) -> Vec<String> {
+    let mut differences = Vec::new();
+    let manifest = read_manifest();
+    let known = manifest.iter().filter(|expected| expected.answer != scaffold::TODO_ANSWER);
+    for expected in known.filter(|expected| expected.day == day) {
+        let label = format!("{}{} {}", expected.day, expected.part, expected.input);
+        let solution = runner::SOLUTIONS
+            .iter()
+            .find(|solution| solution.day == day && solution.part.to_string() == expected.part);
+        let Some(solution) = solution else {
+            differences.push(format!("{}: no solution for line {}", label, expected.line_number));
+            continue;
+        };
+        let file_name = solution.input_file_name(Some(&expected.input));
+        if !Path::new(&file_name).exists() {
+            differences.push(format!("{}: {} does not exist", label, file_name));
+            continue;
+        }
+        let Solver::Function(solve) = solution.solver else {
+            differences.push(format!("{}: {} can't be run", label, solution.name));
+            continue;
+        };
+        match panic::catch_unwind(|| solve(Input::File(&file_name))) {
+            Ok(Ok(answer)) if answer.value == expected.answer => {}
+            Ok(Ok(answer)) => differences.push(format!("{}: expected {}, got {}", label, expected.answer, answer)),
+            Ok(Err(err)) => differences.push(format!("{}: expected {}, failed with {}", label, expected.answer, err)),
+            Err(_) => differences.push(format!("{}: expected {}, panicked", label, expected.answer)),
+        }
+    }
+    differences
+}
+
+fn assert_day(day: Day) {
+    let differences = check_day(day);
+    if !differences.is_empty() {
+        panic!("Day {} differs from {}:\n  {}\n", day, MANIFEST, differences.join("\n  "));
+    }
+}
+
+#[test]
+fn manifest_covers_every_day() {
+    let manifest = read_manifest();
+    let missing: Vec<Day> = (1..=25).filter(|day| !manifest.iter().any(|expected| expected.day == *day)).collect();
+    assert!(missing.is_empty(), "No expected answers for days {:?}", missing);
+}
+
+macro_rules! day_tests {
+    ($($name:ident: $day:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                assert_day($day);
+            }
+        )*
+    };
+}
+
+day_tests! {
+    day_01: 1,
+    day_02: 2,
+    day_03: 3,
+    day_04: 4,
+    day_05: 5,
+    day_06: 6,
+    day_07: 7,
+    day_08: 8,
+    day_09: 9,
+    day_10: 10,
+    day_11: 11,
+    day_12: 12,
+    day_13: 13,
+    day_14: 14,
+    day_15: 15,
+    day_16: 16,
+    day_17: 17,
+    day_18: 18,
+    day_19: 19,
+    day_20: 20,
+    day_21: 21,
+    day_22: 22,
+    day_23: 23,
+    day_24: 24,
+    day_25: 25,
+}
+
+
+//The example needs a different row and search area to the real input, which the runner can't pass
+#[test]
+fn day_15_example() {
+    assert_eq!(fifteen::count_non_covered_positions_in_row(Input::File("15/test_input.txt"), 10).unwrap(), 26);
+    assert_eq!(fifteen::find_tuning_frequency(Input::File("15/test_input.txt"), 20).unwrap(), 56000011);
+}
@@ -0,0 +1,81 @@
+//Every day's solutions for 2022, new-day adds to these
+
+use aoc_common::runner::{Part, Solution, Solver, Year};
+use aoc_common::scaffold::Layout;
+use aoc_common::solution;
+
+use crate::{
+    eight, eighteen, eleven, fifteen, five, four, fourteen, nine, nineteen, one, seven, seventeen,
+    six, sixteen, ten, thirteen, three, twelve, twenty, twentyfive, twentyfour, twentyone,
+    twentythree, twentytwo, two,
+};
+
+pub const YEAR: Year = Year {
+    year: 2022,
+    directory: env!("CARGO_MANIFEST_DIR"),
+    solutions: SOLUTIONS,
+    layout: Layout {
+        template: "src/template.rs",
+        lib: "src/lib.rs",
+        runner: "src/runner.rs",
+        regression: "src/regression.rs",
+        expected_answers: "expected_answers.txt",
+    },
+};
+
+pub const SOLUTIONS: &[Solution] = &[
+    solution!(1, A, one::_1a),
+    solution!(1, B, one::_1b),
+    solution!(2, A, two::_2a),
+    solution!(2, B, two::_2b),
+    solution!(3, A, three::_3a),
+    solution!(3, B, three::_3b),
+    solution!(4, A, four::_4a),
+    solution!(4, B, four::_4b),
+    solution!(5, A, five::_5a),
+    solution!(5, B, five::_5b),
+    solution!(6, A, six::_6a),
+    solution!(6, B, six::_6b),
+    solution!(7, A, seven::_7a),
+    solution!(7, B, seven::_7b),
+    solution!(8, A, eight::_8a),
+    solution!(8, B, eight::_8b),
+    solution!(9, A, nine::_9a),
+    solution!(9, B, nine::_9b),
+    solution!(10, Both, ten::_10a_and_10b),
+    solution!(11, A, eleven::_11a),
+    solution!(11, B, eleven::_11b),
+    solution!(12, A, twelve::_12a),
+    solution!(12, B, twelve::_12b),
+    solution!(13, A, thirteen::_13a),
+    solution!(13, B, thirteen::_13b),
+    solution!(14, A, fourteen::_14a),
+    solution!(14, B, fourteen::_14b),
+    solution!(15, A, fifteen::_15a),
+    solution!(15, B, fifteen::_15b),
+    solution!(16, A, sixteen::_16a),
+    solution!(16, B, sixteen::_16b),
+    solution!(17, A, seventeen::_17a),
+    solution!(17, B, seventeen::_17b),
+    solution!(18, A, eighteen::_18a),
+    solution!(18, B, eighteen::_18b),
+    solution!(19, A, nineteen::_19a),
+    solution!(19, B, nineteen::_19b),
+    solution!(20, A, twenty::_20a),
+    solution!(20, B, twenty::_20b),
+    solution!(21, A, twentyone::_21a),
+    solution!(21, B, twentyone::_21b),
+    solution!(22, A, twentytwo::_22a),
+    solution!(22, B, twentytwo::_22b),
+    solution!(23, A, twentythree::_23a),
+    solution!(23, B, twentythree::_23b),
+    solution!(24, A, twentyfour::_24a),
+    solution!(24, B, twentyfour::_24b),
+    solution!(25, A, twentyfive::_25a),
+    Solution {
+        day: 25,
+        part: Part::B,
+        name: "-",
+        solver: Solver::NotAvailable("there is no puzzle for 25b, the star comes from finishing all the others"),
+    },
+];
@@ -1,15 +1,12 @@
-use std::io::Error;
 use std::collections::HashMap;
 use lazy_static::lazy_static;
 use regex::Regex;
+use crate::error::{Error, ParseResult};
 use crate::utils;
 
-const FILENAME: &str = "7/input.txt";
-//const FILENAME: &str = "7/example.txt";
-
-pub fn _7a() -> Result<u64, Error> {
-    utils::process_file(
-        FILENAME,
+pub fn _7a(input: utils::Input) -> Result<u64, Error> {
+    utils::try_process_file(
+        input,
         parse_line,
         Directories::new(),
         accumulate,
@@ -17,9 +14,9 @@ pub fn _7a() -> Result<u64, Error> {
     )
 }
 
-pub fn _7b() -> Result<u64, Error> {
-    utils::process_file(
-        FILENAME,
+pub fn _7b(input: utils::Input) -> Result<u64, Error> {
+    utils::try_process_file(
+        input,
         parse_line,
         Directories::new(),
         accumulate,
@@ -28,73 +25,59 @@ pub fn _7b() -> Result<u64, Error> {
 }
 
 struct Directories {
-    current: Box<Vec<String>>,
-    dir_sizes: Box<HashMap<String, u64>>,
+    current: Vec<String>,
+    dir_sizes: HashMap<String, u64>,
     current_level_size: u64,
 }
 
 impl Directories {
     pub fn new() -> Directories {
         Directories {
-            current: Box::new(Vec::new()),
-            dir_sizes: Box::new(HashMap::new()),
+            current: Vec::new(),
+            dir_sizes: HashMap::new(),
             current_level_size: 0,
         }
     }
 }
 
 enum Line {
-    CD { dir: String },
-    LS,
-    DIR { _dir: String },
-    FILE { name: String, size: u64 }
+    Cd { dir: String },
+    Ls,
+    Dir { _dir: String },
+    File { name: String, size: u64 }
 }
 
-fn parse_line(line: String) -> Option<Line> {
+fn parse_line(line: &str) -> ParseResult<Line> {
     lazy_static! {
         static ref CD: Regex = Regex::new(r"^\$ cd (.*)$").unwrap();
         static ref LS: Regex = Regex::new(r"^\$ ls.*$").unwrap();
         static ref DIR: Regex = Regex::new(r"^dir (.*)$").unwrap();
         static ref FILE: Regex = Regex::new(r"^(\d+) (.*)$").unwrap();
     }
-    None.or_else(||
-            CD.captures(&line).map(|cd_caps|
-                Line::CD {
-                    dir: String::from(cd_caps.get(1).unwrap().as_str())
-                }
-            )
-        ).or_else(||
-            LS.captures(&line).map(|_|
-                Line::LS
-            )
-        ).or_else(||
-            DIR.captures(&line).map(|dir_caps|
-                Line::DIR {
-                    _dir: String::from(dir_caps.get(1).unwrap().as_str())
-                }
-            )
-        ).or_else(||
-            FILE.captures(&line).map(|file_caps|
-                Line::FILE {
-                    name: String::from(file_caps.get(2).unwrap().as_str()),
-                    size: file_caps.get(1).unwrap().as_str().parse().unwrap()
-                }
-            )
-        )
+    if let Some(cd_caps) = CD.captures(line) {
+        Ok(Line::Cd { dir: String::from(&cd_caps[1]) })
+    } else if LS.is_match(line) {
+        Ok(Line::Ls)
+    } else if let Some(dir_caps) = DIR.captures(line) {
+        Ok(Line::Dir { _dir: String::from(&dir_caps[1]) })
+    } else if let Some(file_caps) = FILE.captures(line) {
+        Ok(Line::File { name: String::from(&file_caps[2]), size: file_caps[1].parse()? })
+    } else {
+        Err("expected a cd or ls command, or a dir or file that ls listed".into())
+    }
 }
 
-fn accumulate(directories: Directories, maybe_line: Option<Line>) -> Directories {
-    match maybe_line {
-        Some(Line::CD{ dir }) => cd(directories, dir),
-        Some(Line::FILE{ name, size }) => file(directories, name, size),
-        Some(Line::LS) => directories,
-        Some(Line::DIR{ _dir }) => directories,
-        _ => directories,
+fn accumulate(directories: Directories, line: Line) -> Directories {
+    match line {
+        Line::Cd{ dir } => cd(directories, dir),
+        Line::File{ name, size } => file(directories, name, size),
+        Line::Ls => directories,
+        Line::Dir{ _dir } => directories,
     }
 }
 
 fn cd(mut directories: Directories, dir: String) -> Directories {
-    //println!("cd {}", dir);
+    //eprintln!("cd {}", dir);
     let current_dir = build_dir_name(&directories.current);
     //changing directories, stash/increment the size for the current directory
     let current_dir_size = increment_dir_size(&mut directories.dir_sizes, current_dir, directories.current_level_size);
@@ -112,24 +95,24 @@ fn cd(mut directories: Directories, dir: String) -> Directories {
     directories
 }
 
-fn build_dir_name(dirs: &Box<Vec<String>>) -> String {
+fn build_dir_name(dirs: &[String]) -> String {
     let mut s = String::new();
     for dir in dirs.iter() {
-        s.push_str("/");
+        s.push('/');
         s.push_str(dir);
     }
     s
 }
 
-fn increment_dir_size(dir_sizes: &mut Box<HashMap<String, u64>>, dir_name: String, increment: u64) -> u64 {
-    if dir_name.len() > 0 {
+fn increment_dir_size(dir_sizes: &mut HashMap<String, u64>, dir_name: String, increment: u64) -> u64 {
+    if !dir_name.is_empty() {
         //let dir_name_copy = dir_name.clone();
         let value = dir_sizes
             .entry(dir_name)
             .and_modify(|size| *size += increment)
             .or_insert(increment);
-        //println!("Incremented {} by {}, now {}", dir_name_copy, increment, *value);
-        0 + *value
+        //eprintln!("Incremented {} by {}, now {}", dir_name_copy, increment, *value);
+        *value
     } else {
         0
     }
@@ -142,7 +125,7 @@ fn file(mut directories: Directories, _name: String, size: u64) -> Directories {
 
 fn navigate_back_to_top(mut directories: Directories) -> Directories {
     //navigate back to the top level
-    while directories.current.len() > 0 {
+    while !directories.current.is_empty() {
         directories = cd(directories, String::from(".."));
     }
     directories
@@ -164,12 +147,12 @@ fn reduce2(mut directories: Directories) -> u64 {
     let root_space = directories.dir_sizes.get("//").unwrap();
     let unused_space: u64 = DISK_SPACE - root_space;
     let need_to_free = SPACE_NEEDED - unused_space;
-    println!("root={}, unused={}, need_to_free={}", root_space, unused_space, need_to_free);
+    eprintln!("root={}, unused={}, need_to_free={}", root_space, unused_space, need_to_free);
 
     let mut filtered = Vec::from_iter(
         directories.dir_sizes.values()
             .filter(|v| **v >= need_to_free)
-            .map(|v| *v )
+            .copied()
     );
     filtered.sort();
     *filtered.first().unwrap()
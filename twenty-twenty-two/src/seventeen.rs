@@ -1,267 +1,376 @@
-use std::{str::Chars, collections::{HashSet, BTreeMap}};
+use std::{str::{Chars, FromStr}, fmt::Display};
 
+use crate::error::Error;
 use crate::utils;
 
-const FILE_NAME: &str = "17/input.txt";
-//const FILE_NAME: &str = "17/test_input.txt";
+pub fn _17a(input: utils::Input) -> Result<i64, Error> {
+    tower_height(input, &Chamber::standard(), NUM_ROCKS_1)
+}
+
+pub fn _17b(input: utils::Input) -> Result<i64, Error> {
+    repeating_tower_height(input, &Chamber::standard(), NUM_ROCKS_2)
+}
+
+///Height of the tower once num_rocks have fallen into the chamber, dropping every one of them
+pub fn tower_height(input: utils::Input, chamber: &Chamber, num_rocks: usize) -> Result<i64, Error> {
+    utils::process_file(input, parse_line, State::new(), accumulate, |state| reduce(&state, chamber, num_rocks))?
+        .map_err(|message| Error::format(input.name(), message))
+}
 
-pub fn _17a() -> Result<i64, std::io::Error> {
-    utils::process_file(FILE_NAME, parse_line, State::new(), accumulate, reduce1)
+///Height of the tower once num_rocks have fallen into the chamber, working it out from where the
+/// tower starts to repeat so that it can be used for far more rocks than could be dropped
+pub fn repeating_tower_height(input: utils::Input, chamber: &Chamber, num_rocks: usize) -> Result<i64, Error> {
+    utils::process_file(input, parse_line, State::new(), accumulate, |state| reduce2(state, chamber, num_rocks))?
+        .map_err(|message| Error::format(input.name(), message))
 }
 
-pub fn _17b() -> Result<i64, std::io::Error> {
-    utils::process_file(FILE_NAME, parse_line, State::new(), accumulate, reduce2)
+///Read the jets of gas without doing anything with them, for benchmarking [reduce2]
+pub fn read_winds(input: utils::Input) -> Result<State, Error> {
+    utils::process_file(input, parse_line, State::new(), accumulate, |state| state)
 }
 
 fn parse_line(line: String) -> String {
     line
 }
 
-struct State {
+#[derive(Clone)]
+pub struct State {
     winds: Option<String>,
 }
 
 impl State {
-    pub fn new() -> State {
+    pub(crate) fn new() -> State {
         State { winds: None }
     }
 }
 
 fn accumulate(mut state: State, line: String) -> State {
-    println!("Line length is {}", line.len());
+    eprintln!("Line length is {}", line.len());
     state.winds = Some(line);
     state
 }
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct OccupiedSpace {
-    x: i32,
-    y: i64,
+///Each row of the rock as a bit per space, from the bottom up, with its left edge at bit 0
+struct Rock {
+    rows: Vec<u8>,
+    width: i32,
 }
 
-impl OccupiedSpace {
-    pub fn new(x: i32, y: i64) -> OccupiedSpace {
-        OccupiedSpace { x, y }
+impl Rock {
+    pub fn new(rows: Vec<u8>, width: i32) -> Rock {
+        Rock { rows, width }
     }
-    pub fn to_absolute(&self, x: i32, y: i64) -> OccupiedSpace {
-        OccupiedSpace {
-            x: self.x + x,
-            y: self.y + y,
+}
+
+///Parse a rock drawn as in the puzzle, with '#' for rock and '.' for space
+fn parse_rock(lines: &[String]) -> Result<Rock, String> {
+    let mut spaces = Vec::new();
+    //Drawn from the top down, but y goes up from the bottom of the rock
+    for (y, line) in lines.iter().rev().enumerate() {
+        for (x, c) in line.trim_end().chars().enumerate() {
+            match c {
+                '#' => spaces.push((x, y)),
+                '.' => {}
+                _ => return Err(format!("unexpected '{}' in a rock, draw it with '#' and '.'", c)),
+            }
         }
     }
+    //Make the bottom left of the rock 0, 0 even if the drawing has space around it
+    let min_x = spaces.iter().map(|&(x, _)| x).min().ok_or("there's no '#' in the rock")?;
+    let min_y = spaces.iter().map(|&(_, y)| y).min().unwrap();
+    let width = spaces.iter().map(|&(x, _)| x - min_x + 1).max().unwrap();
+    if width > 8 {
+        return Err(format!("the rock is {} wide, but a chamber is at most 8", width));
+    }
+    let mut rows = vec![0u8; spaces.iter().map(|&(_, y)| y - min_y + 1).max().unwrap()];
+    for (x, y) in spaces {
+        rows[y - min_y] |= 1 << (x - min_x);
+    }
+    Ok(Rock::new(rows, width as i32))
 }
 
-struct Rock {
-    relative_occupieds: Vec<OccupiedSpace>,
+///The rocks that fall, in the order that they fall, and the chamber they fall into
+pub struct Chamber {
+    width: i32,
+    ///Where each rock appears, how far its left edge is from the left wall
+    drop_left: i32,
+    ///and how far its bottom edge is above the tower (or the floor)
+    drop_height: i64,
+    rocks: Vec<Rock>,
 }
 
-impl Rock {
-    pub fn new(relative_occupieds: Vec<OccupiedSpace>) -> Rock {
-        Rock { relative_occupieds }
+///The puzzle's chamber and rocks, in the format read by [Chamber::read]
+pub const STANDARD_CHAMBER: &str = "\
+width: 7
+drop left: 2
+drop height: 3
+
+####
+
+.#.
+###
+.#.
+
+..#
+..#
+###
+
+#
+#
+#
+#
+
+##
+##
+";
+
+impl Chamber {
+    pub fn standard() -> Chamber {
+        Chamber::read(utils::Input::Text(STANDARD_CHAMBER)).expect("the standard chamber can be read")
+    }
+
+    ///Read a chamber from records (see [utils::records]). The first can be settings, one a line:
+    /// `width: 7`, `drop left: 2` and `drop height: 3`, where those left out are as in the puzzle.
+    /// Each of the other records is a rock drawn with '#' and '.', as in the puzzle.
+    ///
+    ///Each row of the chamber is kept in a byte, so it can be at most 8 wide, and only the top
+    /// rows of the tower are kept, so a rock can be at most that tall.
+    pub fn read(input: utils::Input) -> Result<Chamber, Error> {
+        let mut chamber = Chamber {
+            width: 7,
+            drop_left: 2,
+            drop_height: 3,
+            rocks: Vec::new(),
+        };
+        for record in utils::records(input)? {
+            let record = record?;
+            let is_settings = record.lines().iter().all(|line| line.contains(':'));
+            if is_settings && chamber.rocks.is_empty() {
+                record.try_parse_lines(|line| chamber.set(line))?;
+                continue;
+            }
+            let rock = parse_rock(record.lines()).map_err(|message| record.error(message))?;
+            if rock.rows.len() > ROWS_KEPT {
+                return Err(record.error(format!("the rock is {} tall, but at most {} rows are kept", rock.rows.len(), ROWS_KEPT)));
+            }
+            if chamber.drop_left + rock.width > chamber.width {
+                return Err(record.error(format!("the rock doesn't fit in the chamber {} from the left wall", chamber.drop_left)));
+            }
+            chamber.rocks.push(rock);
+        }
+        if chamber.rocks.is_empty() {
+            return Err(Error::format(input.name(), "there are no rocks"));
+        }
+        if !(1..=8).contains(&chamber.width) || chamber.drop_left < 0 || chamber.drop_height < 0 {
+            return Err(Error::format(input.name(), "the width has to be from 1 to 8, and the drop can't be negative"));
+        }
+        Ok(chamber)
+    }
+
+    fn set(&mut self, line: &str) -> Result<(), String> {
+        let (name, value) = line.split_once(':').ok_or("expected a setting, e.g. 'width: 7'")?;
+        match name.trim() {
+            "width" => self.width = parse_setting(value)?,
+            "drop left" => self.drop_left = parse_setting(value)?,
+            "drop height" => self.drop_height = parse_setting(value)?,
+            _ => return Err(format!("unknown setting '{}'", name.trim())),
+        }
+        Ok(())
     }
 }
 
+fn parse_setting<T: FromStr>(value: &str) -> Result<T, String>
+    where T::Err: Display
+{
+    value.trim().parse().map_err(|err| format!("'{}' isn't a number: {}", value.trim(), err))
+}
+
+#[derive(Clone, Copy)]
 enum Direction {
     Left,
     Right,
 }
 
 const LEFT_WALL: i32 = -1;
-const RIGHT_WALL: i32 = 7;
 const FLOOR_HEIGHT: i64 = -1;
 
-const DROP_LEFT: i32 = 2;
-const DROP_HEIGHT: i64 = 3;
-
 const NUM_ROCKS_1: usize = 2022;
-const NUM_ROCKS_2: usize = 1000000000000;
-//const NUM_ROCKS_2: usize = 2022;
+pub const NUM_ROCKS_2: usize = 1000000000000;
 
 const REPORT_EVERY: usize = 100000000;
-const MAX_OCCUPIED_ROWS: usize = 100;
-
-fn reduce1(state: State) -> i64 {
-    reduce(&state, NUM_ROCKS_1)
+///How many of the top rows of the tower are kept, a rock falling any further than that is an error
+const ROWS_KEPT: usize = 128;
+
+///Drops rocks until the tower starts repeating itself, and then works out the height from the
+/// repeats for as many rocks as there are to fall
+pub fn reduce2(state: State, chamber: &Chamber, num_rocks: usize) -> Result<i64, String> {
+    let mut tower = Tower::new(&state, chamber)?;
+    //heights[n] is the height once n rocks have fallen
+    let mut heights = Vec::new();
+    let mut fell_too_far = None;
+    //Every rock in the repeat has been dropped without falling out of the rows kept, and the key
+    // is the same at either end of it, so all the repeats after it fall the same way
+    let keys = (0..=num_rocks).map_while(|rock_num| {
+        if rock_num > 0 {
+            if let Err(message) = tower.drop_rock() {
+                fell_too_far = Some(message);
+                return None;
+            }
+        }
+        heights.push(tower.latest_height);
+        Some(tower.key())
+    });
+    let cycle = utils::find_cycle(keys);
+    if let Some(message) = fell_too_far {
+        return Err(message);
+    }
+    let Some(cycle) = cycle else {
+        eprintln!("No repeat within {} rocks", num_rocks);
+        return Ok(heights[num_rocks]);
+    };
+    let cycle_height = heights[cycle.start + cycle.length] - heights[cycle.start];
+    eprintln!(
+        "Found repeat: {} rocks from {}, growing the tower by {}",
+        cycle.length, cycle.start, cycle_height
+    );
+    Ok(heights[cycle.first_seen(num_rocks)] + cycle.times_round(num_rocks) as i64 * cycle_height)
 }
 
-fn reduce2(state: State) -> i64 {
-    //Start with something suitably high that a repeat will have set in
-    let start_num = 5000;
-    let start_height = reduce(&state, start_num);
-
-    //Now try to find where to repeat (multiples of the number of rocks)
-    let rocks = generate_rocks();
-    let search_increment = rocks.len();
-    let mut repeat_size = search_increment;
-    let repeat_height;
-    loop {
-        println!("Trying {}", repeat_size);
-        let height1 = reduce(&state, start_num + repeat_size);
-        let height2 = reduce(&state, start_num + 2 * repeat_size);
-        let height3 = reduce(&state, start_num + 3 * repeat_size);
-        if height1 - start_height == height2 - height1 &&
-           height2 - height1 == height3 - height2 {
-            println!("Found repeat: {}", repeat_size);
-            repeat_height = height2 - height1;
-            println!("Repeat height is: {}", repeat_height);
-            break;
+fn reduce(state: &State, chamber: &Chamber, num_rocks: usize) -> Result<i64, String> {
+    let mut tower = Tower::new(state, chamber)?;
+    for rock_num in 0..num_rocks {
+        tower.drop_rock()?;
+
+        if rock_num > 0 && rock_num % REPORT_EVERY == 0 {
+            eprintln!("{}", rock_num);
         }
-        repeat_size += search_increment;
     }
+    Ok(tower.latest_height)
+}
 
-    //Finally we can do the calc
-    let remaining_to_calculate = NUM_ROCKS_2 - start_num;
-    let num_repeats_needed = (remaining_to_calculate / repeat_size) - 1;
-    let final_to_calculate = remaining_to_calculate - (repeat_size * num_repeats_needed);
-    let final_height = reduce(&state, final_to_calculate + start_num) - start_height;
-    start_height + (num_repeats_needed as i64 * repeat_height) + final_height
+///The rocks that have come to rest, and the next rock and jet of gas
+struct Tower<'a> {
+    chamber: &'a Chamber,
+    winds: Vec<Direction>,
+    next_rock: usize,
+    next_wind: usize,
+    latest_height: i64,
+    ///The top rows of the tower, a bit per space, with row y at y % ROWS_KEPT
+    rows: [u8; ROWS_KEPT],
 }
 
-fn reduce(state: &State, num_rocks: usize) -> i64 {
-    let rocks = generate_rocks();
-    let mut chars = state.winds.as_ref().unwrap().chars();
-    let mut latest_height: i64 = 0;
-
-    let mut next_direction_and_reset = || {
-        let mut direction = get_next_direction(&mut chars);
-        let mut reset = false;
-        while direction.is_none() {
-            chars = state.winds.as_ref().unwrap().chars();
-            reset = true;
-            direction = get_next_direction(&mut chars);
+impl Tower<'_> {
+    fn new<'a>(state: &State, chamber: &'a Chamber) -> Result<Tower<'a>, String> {
+        let mut chars = state.winds.as_deref().unwrap_or_default().chars();
+        let winds: Vec<Direction> = std::iter::from_fn(|| get_next_direction(&mut chars)).collect();
+        if winds.is_empty() {
+            return Err(String::from("there are no jets of gas, expected a line of '<' and '>'"));
         }
-        (direction.unwrap(), reset)
-    };
-
-    let mut occupied_spaces: BTreeMap<i64, HashSet<i32>> = BTreeMap::new();
-
-    for rock_num in 0..num_rocks {
+        Ok(Tower {
+            chamber,
+            winds,
+            next_rock: 0,
+            next_wind: 0,
+            latest_height: 0,
+            rows: [0; ROWS_KEPT],
+        })
+    }
 
-        let rock_mod = rock_num % rocks.len();
-        let rock = rocks.get(rock_mod).unwrap();
+    ///Drop the next rock, with the jets of gas pushing it, until it comes to rest. It is an error
+    /// for it to fall below the rows kept, as what is there has been forgotten.
+    fn drop_rock(&mut self) -> Result<(), String> {
+        let chamber = self.chamber;
+        let rock = &chamber.rocks[self.next_rock];
+        self.next_rock = (self.next_rock + 1) % chamber.rocks.len();
 
-        let mut left = DROP_LEFT;
-        let mut height = latest_height + DROP_HEIGHT;
+        let mut left = chamber.drop_left;
+        let mut height = self.latest_height + chamber.drop_height;
         let mut fell = true;
 
-        output_tower(&rock, None, left, height, latest_height, &occupied_spaces);
-
         while fell {
-            let (direction, reset) = next_direction_and_reset();
-            if reset {
-                // println!("Reset at rock_num {}, mod {}, with latest_height {} after {} moves", rock_num, rock_mod, latest_height, num_rock_moves);
+            let direction = self.winds[self.next_wind];
+            self.next_wind = (self.next_wind + 1) % self.winds.len();
+
+            left = wind_push(self, rock, left, height, &direction);
+            if self.forgotten(height - 1) {
+                return Err(format!(
+                    "a rock fell more than {} rows into the tower, below the rows that are kept",
+                    ROWS_KEPT
+                ));
             }
+            (height, fell) = rock_falls(self, rock, left, height);
+        }
+        self.place(rock, left, height);
+        Ok(())
+    }
 
-            left = wind_push(&rock, left, height, &direction, &occupied_spaces);
-            output_tower(&rock, Some(&direction), left, height, latest_height, &occupied_spaces);
-            (height, fell)  = rock_falls(&rock, left, height, &occupied_spaces);
-            output_tower(&rock, None, left, height, latest_height, &occupied_spaces);
-            if !fell {
-                for occupied in rock.relative_occupieds.iter() {
-                    let absolute_occupied = occupied.to_absolute(left, height);
-                    latest_height = std::cmp::max(latest_height, absolute_occupied.y + 1); //plus one as block takes up 1
-
-                    let row = occupied_spaces.entry(absolute_occupied.y).or_insert_with(|| HashSet::new());
-                    row.insert(absolute_occupied.x);
-                }
-                output_tower(&rock, None, left, height, latest_height, &occupied_spaces);
-            }
+    ///Whether row y is below the rows kept (but above the floor), so what is in it isn't known
+    fn forgotten(&self, y: i64) -> bool {
+        y > FLOOR_HEIGHT && y < self.latest_height - ROWS_KEPT as i64
+    }
+
+    ///The spaces taken in row y, everything is taken in the floor. Rows no longer kept mustn't be
+    /// asked for, see [Tower::forgotten].
+    fn row(&self, y: i64) -> u8 {
+        if y >= self.latest_height {
+            0
+        } else if y <= FLOOR_HEIGHT {
+            u8::MAX
+        } else {
+            self.rows[y as usize % ROWS_KEPT]
         }
+    }
 
-        while occupied_spaces.len() > MAX_OCCUPIED_ROWS {
-            occupied_spaces.pop_first();
+    ///Whether the rock would hit a wall, the floor or the tower with its bottom left at left, height
+    fn collides(&self, rock: &Rock, left: i32, height: i64) -> bool {
+        if left <= LEFT_WALL || left + rock.width > self.chamber.width {
+            return true;
         }
+        rock.rows.iter().zip(height..).any(|(bits, y)| bits << left & self.row(y) != 0)
+    }
 
-        if rock_num > 0 && rock_num % REPORT_EVERY == 0 {
-            println!("{}", rock_num);
+    fn place(&mut self, rock: &Rock, left: i32, height: i64) {
+        for (bits, y) in rock.rows.iter().zip(height..) {
+            let row = &mut self.rows[y as usize % ROWS_KEPT];
+            //Rows above the tower still hold whatever was kept there before
+            if y >= self.latest_height {
+                *row = bits << left;
+            } else {
+                *row |= bits << left;
+            }
         }
+        self.latest_height = self.latest_height.max(height + rock.rows.len() as i64);
     }
 
-    latest_height
+    ///Everything that decides how the rest of the rocks fall: which rock and jet of gas are next,
+    /// and the rows kept at the top of the tower, from the top down
+    fn key(&self) -> (usize, usize, [u8; ROWS_KEPT]) {
+        let rows = std::array::from_fn(|down| self.row(self.latest_height - 1 - down as i64));
+        (self.next_rock, self.next_wind, rows)
+    }
 }
 
-///Try to push the rock in the direction, stopping if it would hit the wall, or an occupied space
+///Try to push the rock in the direction, stopping if it would hit the wall, or the tower
 /// returns the 'new' left (which will not be changed, if it wasn't possible)
-fn wind_push(rock: &Rock, left: i32, height: i64, direction: &Direction, occupied_spaces: &BTreeMap<i64, HashSet<i32>>) -> i32 {
+fn wind_push(tower: &Tower, rock: &Rock, left: i32, height: i64, direction: &Direction) -> i32 {
     let new_left = match direction {
         Direction::Left => left - 1,
         Direction::Right => left + 1,
     };
-    //check each space occupied by the rock...  if any would not be possible, then don't move
-    for space in rock.relative_occupieds.iter() {
-        let absolute = space.to_absolute(new_left, height);
-        //left wall or right wall
-        if absolute.x <= LEFT_WALL ||absolute.x >= RIGHT_WALL {
-            return left;
-        }
-        //is occupied?
-        let occupied_row = occupied_spaces.get(&absolute.y);
-        if let Some(map) = occupied_row {
-            if map.contains(&absolute.x) {
-                return left;
-            }
-        }
+    if tower.collides(rock, new_left, height) {
+        left
+    } else {
+        new_left
     }
-    //all were ok - new left is good:
-    new_left
 }
 
 ///Try to let the rock fall - returning the new height and true if it could, otherwise the original height and false
-fn rock_falls(rock: &Rock, left: i32, height: i64, occupied_spaces: &BTreeMap<i64, HashSet<i32>>) -> (i64, bool) {
-    let new_height = height - 1;
-    //check whether the rock is going to hit anything occupied
-    for space in rock.relative_occupieds.iter() {
-        let absolute = space.to_absolute(left, new_height);
-        //floor
-        if absolute.y <= FLOOR_HEIGHT {
-            return (height, false);
-        }
-        //is occupied?
-        let occupied_row = occupied_spaces.get(&absolute.y);
-        if let Some(map) = occupied_row {
-            if map.contains(&absolute.x) {
-                return (height, false);
-            }
-        }
+fn rock_falls(tower: &Tower, rock: &Rock, left: i32, height: i64) -> (i64, bool) {
+    if tower.collides(rock, left, height - 1) {
+        (height, false)
+    } else {
+        (height - 1, true)
     }
-    (new_height, true)
-}
-
-
-fn generate_rocks() -> Vec<Rock> {
-    let rock1: Rock = Rock::new(vec![
-        OccupiedSpace::new(0, 0),
-        OccupiedSpace::new(1, 0),
-        OccupiedSpace::new(2, 0),
-        OccupiedSpace::new(3, 0),
-    ]);
-    let rock2: Rock = Rock::new(vec![
-        OccupiedSpace::new(1, 0),
-        OccupiedSpace::new(0, 1),
-        OccupiedSpace::new(1, 1),
-        OccupiedSpace::new(2, 1),
-        OccupiedSpace::new(1, 2),
-    ]);
-    let rock3: Rock = Rock::new(vec![
-        OccupiedSpace::new(0, 0),
-        OccupiedSpace::new(1, 0),
-        OccupiedSpace::new(2, 0),
-        OccupiedSpace::new(2, 1),
-        OccupiedSpace::new(2, 2),
-    ]);
-    let rock4: Rock = Rock::new(vec![
-        OccupiedSpace::new(0, 0),
-        OccupiedSpace::new(0, 1),
-        OccupiedSpace::new(0, 2),
-        OccupiedSpace::new(0, 3),
-    ]);
-    let rock5: Rock = Rock::new(vec![
-        OccupiedSpace::new(0, 0),
-        OccupiedSpace::new(1, 0),
-        OccupiedSpace::new(0, 1),
-        OccupiedSpace::new(1, 1),
-    ]);
-    vec![rock1, rock2, rock3, rock4, rock5]
 }
 
 fn get_next_direction(chars: &mut Chars) -> Option<Direction> {
@@ -271,40 +380,3 @@ fn get_next_direction(chars: &mut Chars) -> Option<Direction> {
         _ => None,
     }
 }
-
-fn output_tower(_last_rock: &Rock, _direction: Option<&Direction>, _rock_left: i32, _rock_height: i64, _max_height: i64,
-     _occupied_spaces: &BTreeMap<i64, HashSet<i32>>)
-{
-}
-
-// fn output_tower(last_rock: &Rock, direction: Option<&Direction>, rock_left: i32, rock_height: i32, max_height: i32,
-//     occupied_spaces: &HashSet<OccupiedSpace>)
-// {
-//     match direction {
-//         Some(Direction::Left) => println!("<-- Left"),
-//         Some(Direction::Right) => println!("Right -->"),
-//         None => {},
-//     }
-//     for height in (0..max_height + DROP_HEIGHT + 3).rev() {
-//         'inner: for x in 0..RIGHT_WALL {
-//             let space = OccupiedSpace::new(x, height);
-//             //is this occupied by the rock?
-//             for rock_relative in last_rock.relative_occupieds.iter() {
-//                 let absolute = rock_relative.to_absolute(rock_left, rock_height);
-//                 if space == absolute {
-//                     print!("@");
-//                     continue 'inner;
-//                 }
-//             }
-//             //is this occupied
-//             if occupied_spaces.contains(&space) {
-//                 print!("#");
-//                 continue 'inner;
-//             }
-//             //not occupied
-//             print!(".");
-//         }
-//         println!("");
-//     }
-//     println!("");
-// }
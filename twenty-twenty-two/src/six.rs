@@ -1,26 +1,20 @@
-use std::io::Error;
 use std::collections::{VecDeque, HashMap};
-use std::convert::identity;
+use crate::error::Error;
 use crate::utils;
 
-const FILENAME: &str = "6/input.txt";
-
-pub fn _6a() -> Result<usize, Error> {
-    run(|info, line| accumulate(info, line, 4))
+pub fn _6a(input: utils::Input) -> Result<usize, Error> {
+    find_marker(input, 4)
 }
 
-pub fn _6b() -> Result<usize, Error> {
-    run(|info, line| accumulate(info, line, 14))
+pub fn _6b(input: utils::Input) -> Result<usize, Error> {
+    find_marker(input, 14)
 }
 
-fn run(accumulate_fn: fn (Info, String) -> Info) -> Result<usize, Error> {
-    utils::process_file(
-        FILENAME,
-        identity,
-        Info::new(),
-        accumulate_fn,
-        reduce
-    )
+///Find how many characters need to be read before the last len_to_detect are all different
+pub fn find_marker(input: utils::Input, len_to_detect: usize) -> Result<usize, Error> {
+    utils::lines(input)?
+        .try_fold(Info::new(), |info, line| line.map(|line| accumulate(info, line, len_to_detect)))
+        .map(reduce)
 }
 
 #[derive(Debug)]
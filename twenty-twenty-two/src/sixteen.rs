@@ -1,30 +1,33 @@
-//FIXME: Yuckie stuff for handling multiple ids
 use core::fmt;
-use std::cmp::{self, Ordering};
-use std::collections::{HashMap, HashSet};
-use std::io;
+use std::collections::HashMap;
 use std::rc::Rc;
 
-use lazy_static::__Deref;
-
+use crate::error::{Error, ParseResult};
+use crate::search;
 use crate::utils;
 
 type ValveID = String;
 type FlowRate = usize;
 type TotalPressure = usize;
+///A set of the valves worth opening, bit i for [Tunnels::valves]\[i\]
+type ValveSet = usize;
 
-const FILE_NAME: &str = "16/input.txt";
-//const FILE_NAME: &str = "16/test_input.txt";
+pub fn _16a(input: utils::Input) -> Result<TotalPressure, Error> {
+    utils::try_process_file(input, parse_line, State::new(), accumulate, reduce1)?
+        .map_err(|message| Error::format(input.name(), message))
+}
 
-pub fn _16a() -> Result<TotalPressure, std::io::Error> {
-    utils::process_file(FILE_NAME, parse_line, State::new(), accumulate, reduce1)
+pub fn _16b(input: utils::Input) -> Result<TotalPressure, Error> {
+    utils::try_process_file(input, parse_line, State::new(), accumulate, reduce2)?
+        .map_err(|message| Error::format(input.name(), message))
 }
 
-pub fn _16b() -> Result<TotalPressure, std::io::Error> {
-    utils::process_file(FILE_NAME, parse_line, State::new(), accumulate, reduce2)
+///Read the valves without doing anything with them, for benchmarking [best_schedule]
+pub fn read_valves(input: utils::Input) -> Result<State, Error> {
+    utils::try_process_file(input, parse_line, State::new(), accumulate, |state| state)
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Valve {
     id: Rc<ValveID>,
     flow_rate: FlowRate,
@@ -39,46 +42,36 @@ impl fmt::Display for Valve {
     }
 }
 
-const VALVE: &str = "Valve ";
-const HAS_FLOW_RATE: &str = "has flow rate=";
-const TUNNELS_LEAD_TO_VALVE: &str = " tunnels lead to valve";
-
-fn parse_line(line: String) -> Valve {
+fn parse_line(line: &str) -> ParseResult<Valve> {
     //Valve VB has flow rate=20; tunnels lead to valves UU, EY, SG, ZB
-    let mut chars = line.chars();
-    utils::skip(&mut chars, VALVE.len());
-    let id = utils::parse_next_string(&mut chars);
-    utils::skip(&mut chars, HAS_FLOW_RATE.len());
-    let flow_rate: FlowRate = utils::parse_next_number(&mut chars).unwrap();
-    utils::skip(&mut chars, TUNNELS_LEAD_TO_VALVE.len());
-    //Check for plural and consume a space (otherwise the space was
-    //just consumed)
-    if let Some('s') = chars.next() {
-        utils::skip(&mut chars, 1);
+    //or, with just the one tunnel
+    //Valve HH has flow rate=22; tunnel leads to valve GG
+    let mut scanner = utils::Scanner::with_delimiters(line, &[' ', '=', ';', ',']);
+    scanner.expect_literal("Valve")?;
+    let id = scanner.next_token()?;
+    scanner.expect_literal("has flow rate=")?;
+    let flow_rate: FlowRate = scanner.next_int()?;
+    scanner
+        .expect_literal("tunnels lead to valves")
+        .or_else(|_| scanner.expect_literal("tunnel leads to valve"))?;
+    let tunnels_to: Vec<Rc<ValveID>> = scanner.map(|tunnel_to| Rc::new(String::from(tunnel_to))).collect();
+    if tunnels_to.is_empty() {
+        return Err("no tunnels lead from the valve".into());
     }
-    let mut tunnels_to: Vec<Rc<ValveID>> = Vec::new();
-    loop {
-        let tunnel_to = utils::parse_next_string(&mut chars);
-        if tunnel_to.len() == 0 {
-            break;
-        }
-        tunnels_to.push(Rc::new(tunnel_to));
-        //consume space (if following the comma)
-        utils::skip(&mut chars, 1);
-    }
-    Valve {
-        id: Rc::new(id),
+    Ok(Valve {
+        id: Rc::new(String::from(id)),
         flow_rate,
         tunnels_to,
-    }
+    })
 }
 
-struct State {
+#[derive(Clone)]
+pub struct State {
     valves: HashMap<Rc<ValveID>, Valve>,
 }
 
 impl State {
-    pub fn new() -> State {
+    pub(crate) fn new() -> State {
         State {
             valves: HashMap::new(),
         }
@@ -86,265 +79,250 @@ impl State {
 }
 
 fn accumulate(mut state: State, valve: Valve) -> State {
-    println!("{}", valve);
+    eprintln!("{}", valve);
     state.valves.insert(valve.id.clone(), valve);
     state
 }
 
-#[derive(Debug)]
-struct Visit {
-    ids: Vec<Rc<ValveID>>,
-    time_cost_to_reach: usize,
-    released_flow_rate: FlowRate,
-    total_pressure_released: TotalPressure,
-    opened_valves: Rc<HashSet<Rc<ValveID>>>,
-}
+pub const MAX_TIME_1: usize = 30;
+pub const MAX_TIME_2: usize = 26;
 
-impl Visit {
-    pub fn new(
-        ids: Vec<Rc<ValveID>>,
-        time_cost_to_reach: usize,
-        released_flow_rate: FlowRate,
-        total_pressure_released: TotalPressure,
-        opened_valves: Rc<HashSet<Rc<ValveID>>>,
-    ) -> Visit {
-        Visit {
-            ids,
-            time_cost_to_reach,
-            released_flow_rate,
-            total_pressure_released,
-            opened_valves,
-        }
-    }
+fn reduce1(state: State) -> Result<TotalPressure, String> {
+    reduce(&state, 1, MAX_TIME_1)
 }
 
-const MAX_TIME_1: usize = 30;
-const MAX_TO_KEEP1: usize = 10000;
-const MAX_TIME_2: usize = 26;
-const MAX_TO_KEEP2: usize = 20000;
-
-fn reduce1(state: State) -> TotalPressure {
-    reduce(state, 1, MAX_TIME_1, MAX_TO_KEEP1)
+fn reduce2(state: State) -> Result<TotalPressure, String> {
+    reduce(&state, 2, MAX_TIME_2)
 }
 
-fn reduce2(state: State) -> TotalPressure {
-    reduce(state, 2, MAX_TIME_2, MAX_TO_KEEP2)
+fn reduce(state: &State, num_agents: usize, max_time: usize) -> Result<TotalPressure, String> {
+    let schedule = best_schedule(state, num_agents, max_time)?;
+    eprint!("{}", schedule);
+    Ok(schedule.total_pressure)
 }
 
-fn reduce(state: State, num_ids: usize, max_time: usize, max_to_keep: usize) -> TotalPressure {
-    //use the fn or see warnings
-    utils::output_into_iter_io(
-        io::stdout(),
-        " ",
-        &mut vec!["Ready,", "Steady, ", "Go"].iter(),
-    );
-    //calculate the maximum number of valves that it makes sense to open
-    let max_valves_to_open: usize = state
-        .valves
-        .values()
-        .filter(|valve| valve.flow_rate > 0)
-        .count();
-
-    let mut max_total_pressure_released = 0;
-
-    let this_visits: &mut Vec<Visit> = &mut Vec::new();
-    let next_visits: &mut Vec<Visit> = &mut Vec::new();
-    this_visits.push(start_visit(&state, num_ids));
-
-    let mut iteration = 0;
-    while !this_visits.is_empty() {
-        iteration += 1;
-
-        'inner: for visit in this_visits.drain(..) {
-            //Completions
-            if let Some(total_pressure_released) = reached_max_time(&visit, max_time) {
-                max_total_pressure_released =
-                    cmp::max(max_total_pressure_released, total_pressure_released);
-                continue 'inner;
-            }
-            if let Some(total_pressure_released) =
-                all_valves_opened(max_valves_to_open, &visit, max_time)
-            {
-                max_total_pressure_released =
-                    cmp::max(max_total_pressure_released, total_pressure_released);
-                continue 'inner;
-            }
-
-            //Optional next paths
-            for next_visit in turn_on_valve(&visit, &state) {
-                next_visits.push(next_visit);
-            }
-            //Navigate to the others
-            for next_visit in visit_neighbours(&visit, &state) {
-                next_visits.push(next_visit);
-            }
-        }
+///What an agent does in a minute
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    MoveTo(Rc<ValveID>),
+    Open(Rc<ValveID>),
+}
 
-        //limit the number taken forward
-        if next_visits.len() > max_to_keep {
-            println!(
-                "Iteration {}: {} -> {}",
-                iteration,
-                next_visits.len(),
-                max_to_keep
-            );
-            next_visits.sort_by(compare_fitness);
-            next_visits.truncate(max_to_keep);
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::MoveTo(id) => write!(f, "move to {}", id),
+            Action::Open(id) => write!(f, "open {}", id),
         }
-
-        std::mem::swap(this_visits, next_visits);
     }
+}
 
-    max_total_pressure_released
+///How the agents release the most pressure
+#[derive(Debug, Clone)]
+pub struct Schedule {
+    pub total_pressure: TotalPressure,
+    ///What each agent does from the first minute, they wait once they've done it all
+    pub actions: Vec<Vec<Action>>,
 }
 
-fn start_visit(state: &State, num_ids: usize) -> Visit {
-    let start_valve = state.valves.get(&String::from("AA")).unwrap();
-    let mut ids: Vec<Rc<ValveID>> = Vec::new();
-    for _ in 0..num_ids {
-        ids.push(Rc::clone(&start_valve.id))
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (agent, actions) in self.actions.iter().enumerate() {
+            write!(f, "Agent {}: ", agent + 1)?;
+            utils::output_into_iter(f, ", ", &mut actions.iter());
+            writeln!(f)?;
+        }
+        writeln!(f, "Releasing {} pressure", self.total_pressure)
     }
-    Visit::new(ids, 0, 0, 0, Rc::new(HashSet::new()))
 }
 
-///if this visit reaches the maximum time then return Some(total_pressure_released),
-///otherwise, return None
-fn reached_max_time(visit: &Visit, max_time: usize) -> Option<TotalPressure> {
-    if visit.time_cost_to_reach >= max_time {
-        return Some(visit.total_pressure_released);
-    }
-    None
+///The best way for num_agents (at least one, e.g. you and an elephant) starting at AA to release
+/// pressure in max_time
+///
+///Only the valves that release pressure matter, along with how long it takes to get between them.
+/// For every set of those valves, the most that one agent can release by opening just that set is
+/// found, then the valves are shared out between the agents a set each.
+///
+///An error is returned if there is no AA, or a tunnel leads to a valve that isn't listed.
+pub fn best_schedule(state: &State, num_agents: usize, max_time: usize) -> Result<Schedule, String> {
+    let tunnels = Tunnels::new(state)?;
+    let routes = Routes::find(&tunnels, max_time);
+    let (total_pressure, sets) = share_out(&routes, num_agents);
+    let actions = sets
+        .into_iter()
+        .map(|set| {
+            let mut actions = Vec::new();
+            let mut at = Rc::clone(&tunnels.start);
+            for &valve in routes.orders[set].iter() {
+                let to = &tunnels.valves[valve];
+                let path = search::bfs(Rc::clone(&at), |id| state.valves[id].tunnels_to.clone(), |id| id == to)
+                    .expect("the route only goes to valves that can be reached");
+                actions.extend(path.nodes.into_iter().skip(1).map(Action::MoveTo));
+                actions.push(Action::Open(Rc::clone(to)));
+                at = Rc::clone(to);
+            }
+            actions
+        })
+        .collect();
+    Ok(Schedule {
+        total_pressure,
+        actions,
+    })
 }
 
-///if this visit opens all valves then return Some(total_pressure_released) where
-/// total_pressure_released will be calculated as applying the flow rate (which is now maximum)
-/// until the time limit.
-///If not all valves are open, return None
-fn all_valves_opened(
-    number_of_valves: usize,
-    visit: &Visit,
-    max_time: usize,
-) -> Option<TotalPressure> {
-    if visit.opened_valves.len() >= number_of_valves {
-        let final_total_pressure_released = visit.total_pressure_released
-            + visit.released_flow_rate * (max_time - visit.time_cost_to_reach);
-        return Some(final_total_pressure_released);
-    }
-    None
+///The valves worth opening and how far apart they are
+struct Tunnels {
+    start: Rc<ValveID>,
+    ///The valves with a flow rate that can be reached from the start
+    valves: Vec<Rc<ValveID>>,
+    flow_rates: Vec<FlowRate>,
+    ///distances\[i\]\[j\] is the minutes to get from valves\[i\] to valves\[j\], usize::MAX if it can't be done
+    distances: Vec<Vec<usize>>,
+    ///The minutes to get from the start to each valve
+    from_start: Vec<usize>,
 }
 
-///If we are able to turn on any valves, remain here and turn them on, returning
-/// a Vector of next_visits,
-///otherwise we can't turn it on and the returned vector will be empty
-/// FIXME: yuck 1 and 2 handling
-fn turn_on_valve(visit: &Visit, state: &State) -> Vec<Visit> {
-    let mut next_visits: Vec<Visit> = Vec::new();
-    if visit.ids.len() == 1 {
-        let id = visit.ids.get(0).unwrap();
-        let valve = state.valves.get(id).unwrap();
-        if valve.flow_rate > 0 && !visit.opened_valves.contains(&valve.id) {
-            let mut new_opened_valves = visit.opened_valves.deref().clone();
-            new_opened_valves.insert(Rc::clone(id));
+impl Tunnels {
+    fn new(state: &State) -> Result<Tunnels, String> {
+        let start = Rc::clone(&state.valves.get(&String::from("AA")).ok_or("there's no valve AA to start at")?.id);
+        for valve in state.valves.values() {
+            if let Some(to) = valve.tunnels_to.iter().find(|to| !state.valves.contains_key(*to)) {
+                return Err(format!("a tunnel leads from {} to {}, which isn't listed", valve.id, to));
+            }
+        }
+        //The minutes to get from a valve to each of the others
+        let distances_from = |id: &Rc<ValveID>| {
+            search::reachable(Rc::clone(id), |id| state.valves[id].tunnels_to.clone())
+        };
+        let from_start = distances_from(&start);
+        let mut valves: Vec<&Valve> = state
+            .valves
+            .values()
+            .filter(|valve| valve.flow_rate > 0 && from_start.contains_key(&valve.id))
+            .collect();
+        valves.sort_by(|valve1, valve2| valve1.id.cmp(&valve2.id));
+        let distances = valves
+            .iter()
+            .map(|valve| {
+                let distances = distances_from(&valve.id);
+                valves.iter().map(|to| *distances.get(&to.id).unwrap_or(&usize::MAX)).collect()
+            })
+            .collect();
+        Ok(Tunnels {
+            from_start: valves.iter().map(|valve| from_start[&valve.id]).collect(),
+            valves: valves.iter().map(|valve| Rc::clone(&valve.id)).collect(),
+            flow_rates: valves.iter().map(|valve| valve.flow_rate).collect(),
+            distances,
+            start,
+        })
+    }
 
-            next_visits.push(Visit::new(
-                visit.ids.clone(),
-                visit.time_cost_to_reach + 1,
-                visit.released_flow_rate + valve.flow_rate,
-                visit.total_pressure_released + visit.released_flow_rate,
-                Rc::new(new_opened_valves),
-            ));
+    ///The minutes to get to a valve from another, or from the start for None
+    fn distance(&self, from: Option<usize>, to: usize) -> usize {
+        match from {
+            Some(from) => self.distances[from][to],
+            None => self.from_start[to],
         }
     }
-    if visit.ids.len() == 2 {
-        let id1 = visit.ids.get(0).unwrap();
-        let valve1 = state.valves.get(id1).unwrap();
-        let open1 = valve1.flow_rate > 0 && !visit.opened_valves.contains(&valve1.id);
-        let id2 = visit.ids.get(1).unwrap();
-        let valve2 = state.valves.get(id2).unwrap();
-        let open2 = valve2.flow_rate > 0 && !visit.opened_valves.contains(&valve2.id);
+}
+
+///The most pressure a single agent can release by opening exactly the valves in each set, and the
+/// order to open them in. None for sets it can't open in time.
+struct Routes {
+    pressures: Vec<Option<TotalPressure>>,
+    orders: Vec<Vec<usize>>,
+}
 
-        if open1 && open2 && (id1 != id2) {
-            //open both
-            let mut new_opened_valves = visit.opened_valves.deref().clone();
-            new_opened_valves.insert(Rc::clone(id1));
-            new_opened_valves.insert(Rc::clone(id2));
+impl Routes {
+    fn find(tunnels: &Tunnels, max_time: usize) -> Routes {
+        let num_sets = 1 << tunnels.valves.len();
+        let mut routes = Routes {
+            pressures: vec![None; num_sets],
+            orders: vec![Vec::new(); num_sets],
+        };
+        routes.extend(tunnels, max_time, &mut Vec::new(), 0, 0, 0);
+        routes
+    }
 
-            next_visits.push(Visit::new(
-                visit.ids.clone(), //staying where we are on both
-                visit.time_cost_to_reach + 1,
-                visit.released_flow_rate + valve1.flow_rate + valve2.flow_rate,
-                visit.total_pressure_released + visit.released_flow_rate,
-                Rc::new(new_opened_valves),
-            ));
-        } else if open1 || open2 {
-            //open 1 navigate elsewhere for the other
-            let (open_id, open_valve, move_valve) = if open1 {
-                (id1, valve1, valve2)
-            } else {
-                (id2, valve2, valve1)
-            };
-            let mut new_opened_valves = visit.opened_valves.deref().clone();
-            new_opened_valves.insert(Rc::clone(open_id));
-            let new_opened_rc = Rc::new(new_opened_valves);
-            for navigate_to_id in move_valve.tunnels_to.iter() {
-                next_visits.push(Visit::new(
-                    vec!(Rc::clone(open_id), Rc::clone(navigate_to_id)), //staying where we are on both
-                    visit.time_cost_to_reach + 1,
-                    visit.released_flow_rate + open_valve.flow_rate,
-                    visit.total_pressure_released + visit.released_flow_rate,
-                    Rc::clone(&new_opened_rc),
-                ));
+    ///Record the route that opens the valves in order, with time minutes gone, then try going on
+    /// from it to each valve that is still closed
+    fn extend(
+        &mut self,
+        tunnels: &Tunnels,
+        max_time: usize,
+        order: &mut Vec<usize>,
+        opened: ValveSet,
+        time: usize,
+        pressure: TotalPressure,
+    ) {
+        if self.pressures[opened].is_none_or(|best| pressure > best) {
+            self.pressures[opened] = Some(pressure);
+            self.orders[opened].clone_from(order);
+        }
+        for next in 0..tunnels.valves.len() {
+            //It takes a minute to open once there, and it's no use opening in the last minute
+            let distance = tunnels.distance(order.last().copied(), next);
+            if opened & 1 << next != 0 || distance >= max_time.saturating_sub(time + 1) {
+                continue;
             }
+            let opened_at = time + distance + 1;
+            let released = tunnels.flow_rates[next] * (max_time - opened_at);
+            order.push(next);
+            self.extend(tunnels, max_time, order, opened | 1 << next, opened_at, pressure + released);
+            order.pop();
         }
-        //else no valves to open
     }
-    next_visits
 }
 
-///Return a new visit for all the neighbours
-fn visit_neighbours(visit: &Visit, state: &State) -> Vec<Visit> {
-    let mut neighbours = Vec::new();
-    if visit.ids.len() == 1 {
-        let valve = state.valves.get(visit.ids.get(0).unwrap()).unwrap();
-        for id in valve.tunnels_to.iter() {
-            neighbours.push(Visit::new(
-                vec![Rc::clone(id)],
-                visit.time_cost_to_reach + 1,
-                visit.released_flow_rate,
-                visit.total_pressure_released + visit.released_flow_rate,
-                Rc::clone(&visit.opened_valves),
-            ));
+///The most pressure num_agents can release between them, each opening a different set of valves,
+/// along with those sets
+fn share_out(routes: &Routes, num_agents: usize) -> (TotalPressure, Vec<ValveSet>) {
+    let num_sets = routes.pressures.len();
+    //For one agent, the most it can release from each set, only opening some of it, and what it opens
+    let mut one_agent: Vec<(TotalPressure, ValveSet)> = routes
+        .pressures
+        .iter()
+        .enumerate()
+        .map(|(set, pressure)| pressure.map_or((0, 0), |pressure| (pressure, set)))
+        .collect();
+    for bit in (0..).map(|bit| 1 << bit).take_while(|bit| *bit < num_sets) {
+        for set in (0..num_sets).filter(|set| set & bit != 0) {
+            if one_agent[set ^ bit].0 > one_agent[set].0 {
+                one_agent[set] = one_agent[set ^ bit];
+            }
         }
     }
-    if visit.ids.len() == 2 {
-        let valve1 = state.valves.get(visit.ids.get(0).unwrap()).unwrap();
-        let valve2 = state.valves.get(visit.ids.get(1).unwrap()).unwrap();
-        valve1
-            .tunnels_to
-            .iter()
-            .flat_map(|id1| valve2.tunnels_to.iter().map(move |id2| (id1, id2)))
-            .for_each(|(id1, id2)| {
-                neighbours.push(Visit::new(
-                    vec![Rc::clone(id1), Rc::clone(id2)],
-                    visit.time_cost_to_reach + 1,
-                    visit.released_flow_rate,
-                    visit.total_pressure_released + visit.released_flow_rate,
-                    Rc::clone(&visit.opened_valves),
-                ));
-            });
+
+    //The same for each extra agent, which opens a set of its own and leaves the rest to the others
+    let mut agents = vec![one_agent];
+    for _ in 1..num_agents {
+        let others = agents.last().unwrap();
+        let best = (0..num_sets)
+            .map(|set| {
+                let mut best = (others[set].0, 0);
+                //Each subset of set
+                let mut opens = set;
+                while opens > 0 {
+                    if let Some(pressure) = routes.pressures[opens] {
+                        if pressure + others[set ^ opens].0 > best.0 {
+                            best = (pressure + others[set ^ opens].0, opens);
+                        }
+                    }
+                    opens = (opens - 1) & set;
+                }
+                best
+            })
+            .collect();
+        agents.push(best);
     }
-    neighbours
-}
 
-///Prefer Higher total pressure, then higher released flow rate,
-/// then more opened valves, then id
-fn compare_fitness(visit1: &Visit, visit2: &Visit) -> Ordering {
-    Ordering::Equal
-        .then_with(|| {
-            visit2
-                .total_pressure_released
-                .cmp(&visit1.total_pressure_released)
-        })
-        .then_with(|| visit2.released_flow_rate.cmp(&visit1.released_flow_rate))
-        .then_with(|| visit2.opened_valves.len().cmp(&visit1.opened_valves.len()))
+    //Starting with all the valves, see what each agent opens
+    let mut set = num_sets - 1;
+    let mut sets = Vec::new();
+    for agent in agents.iter().rev() {
+        let opens = agent[set].1;
+        sets.push(opens);
+        set ^= opens;
+    }
+    (agents.last().unwrap()[num_sets - 1].0, sets)
 }
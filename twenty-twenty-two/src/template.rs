@@ -1,18 +1,16 @@
+use crate::error::{Error, ParseResult};
 use crate::utils;
 
-//const FILE_NAME: &str = "template/input.txt";
-const FILE_NAME: &str = "template/test_input.txt";
-
-pub fn _template_a() -> Result<u32, std::io::Error> {
-    utils::process_file(FILE_NAME, parse_line, State::new(), accumulate, reduce)
+pub fn _template_a(input: utils::Input) -> Result<u32, Error> {
+    utils::try_process_file(input, parse_line, State::new(), accumulate, reduce)
 }
 
-pub fn _template_b() -> Result<u32, std::io::Error> {
-    utils::process_file(FILE_NAME, parse_line, State::new(), accumulate, reduce)
+pub fn _template_b(input: utils::Input) -> Result<u32, Error> {
+    utils::try_process_file(input, parse_line, State::new(), accumulate, reduce)
 }
 
-fn parse_line(line: String) -> String {
-    line
+fn parse_line(line: &str) -> ParseResult<String> {
+    Ok(String::from(line))
 }
 
 struct State {
@@ -1,15 +1,13 @@
-use std::io::Error;
 use std::collections::HashSet;
 
 use substring::Substring;
 
+use crate::error::{Error, ParseResult};
 use crate::utils;
 
-const FILE_NAME: &str = "10/input.txt";
-
-pub fn _10a_and_10b() -> Result<i64, Error> {
-    utils::process_file(
-        FILE_NAME,
+pub fn _10a_and_10b(input: utils::Input) -> Result<i64, Error> {
+    utils::try_process_file(
+        input,
         parse_func,
         Signal::new(HashSet::from([20, 60, 100, 140, 180, 220])),
         accumulator,
@@ -22,15 +20,16 @@ enum Instruction {
     Noop,
 }
 
-fn parse_func(line: String) -> Option<Instruction> {
+fn parse_func(line: &str) -> ParseResult<Option<Instruction>> {
     let initial = line.substring(0, 4);
-    match initial {
+    let instruction = match initial {
         "addx" => Some(Instruction::AddX {
-            value: line.substring(5, line.len()).parse().unwrap()
+            value: line.substring(5, line.len()).parse()?
         }),
         "noop" => Some(Instruction::Noop),
         _ => None,
-    }
+    };
+    Ok(instruction)
 }
 
 struct Signal {
@@ -46,7 +45,7 @@ impl Signal {
         Signal {
             x: 1,
             cycle: 1,
-            interesting_cycles: interesting_cycles,
+            interesting_cycles,
             sampled_values: Vec::with_capacity(len),
         }
     }
@@ -70,7 +69,7 @@ fn accumulator(mut signal: Signal, instruction: Option<Instruction>) -> Signal {
 
 fn next_cycle(signal: &mut Signal) {
     maybe_sample_value(signal);
-    output_pixel(&signal);
+    output_pixel(signal);
     signal.cycle += 1;
 }
 
@@ -85,13 +84,13 @@ fn output_pixel(signal: &Signal) {
     let horz_pos = (signal.cycle - 1) % 40;
     //Move to next line?
     if horz_pos == 0 {
-        print!("\n")
+        eprintln!()
     }
     //draw pixel
     if is_in_sprite(horz_pos, signal) {
-        print!("#");
+        eprint!("#");
     } else {
-        print!(".");
+        eprint!(".");
     }
 }
 
@@ -101,7 +100,7 @@ fn is_in_sprite(horz_pixel: i64, signal: &Signal) -> bool {
 
 fn reducer(mut signal: Signal) -> i64 {
     maybe_sample_value(&mut signal); //just incase we have the very last as an interesting value
-    print!("\n");
-    print!("\n");
-    signal.sampled_values.iter().fold(0, |acc, value| acc + value)
+    eprintln!();
+    eprintln!();
+    signal.sampled_values.iter().sum()
 }
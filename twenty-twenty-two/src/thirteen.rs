@@ -1,34 +1,37 @@
 use std::fmt::Display;
 use std::cmp::Ordering;
+use std::num::ParseIntError;
+use std::str::FromStr;
 
-use crate::utils;
-const FILE_NAME: &str = "13/input.txt";
-//const FILE_NAME: &str = "13/test_input.txt";
+use crate::error::{Error, ParseResult};
+use crate::utils::{self, Record};
 
-pub fn _13a() -> Result<usize, std::io::Error> {
-    utils::process_file(
-        FILE_NAME,
-        parse_line,
+pub fn _13a(input: utils::Input) -> Result<usize, Error> {
+    utils::try_process_records(
+        input,
+        parse_pair,
         State::new(),
         accumulate1,
         reduce1
     )
 }
 
-pub fn _13b() -> Result<usize, std::io::Error> {
-    utils::process_file(
-        FILE_NAME,
-        parse_line,
+pub fn _13b(input: utils::Input) -> Result<usize, Error> {
+    utils::try_process_records(
+        input,
+        parse_pair,
         State::new(),
         accumulate2,
         reduce2
     )
 }
 
-type Value = i16;
+pub type Value = i16;
 
+///A packet, or a value or list within one. Items are ordered as the distress signal wants them,
+/// so a list of packets can just be sorted.
 #[derive(Debug)]
-enum Item{
+pub enum Item{
     Val{ val: Value },
     List{ vec: Vec<Item> },
 }
@@ -52,7 +55,46 @@ impl Display for Item {
     }
 }
 
-fn parse_line(line: String) -> Option<Item> {
+impl FromStr for Item {
+    type Err = Box<dyn std::error::Error>;
+
+    ///Parse a packet, e.g. [1,[2,3]]
+    fn from_str(s: &str) -> ParseResult<Item> {
+        parse_line(s)
+    }
+}
+
+impl Ord for Item {
+    fn cmp(&self, other: &Item) -> Ordering {
+        compare_left_and_right(self, other)
+    }
+}
+
+impl PartialOrd for Item {
+    fn partial_cmp(&self, other: &Item) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+///Equal by the packet ordering, so 2 and [2] are equal
+impl PartialEq for Item {
+    fn eq(&self, other: &Item) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Item {}
+
+///Each record is a pair of packets, one per line
+fn parse_pair(pair: &Record) -> Result<Vec<Item>, Error> {
+    let items = pair.try_parse_lines(parse_line)?;
+    if items.len() != 2 {
+        return Err(pair.error(format!("expected a pair of packets, found {}", items.len())));
+    }
+    Ok(items)
+}
+
+fn parse_line(line: &str) -> ParseResult<Item> {
     let mut result = None;
     let mut stack: Vec<Vec<Item>> = Vec::new();
     let mut chars: String = String::new();
@@ -60,7 +102,7 @@ fn parse_line(line: String) -> Option<Item> {
         match c {
             '[' => stack.push(Vec::new()),
             ']' => {
-                add_value(&mut chars, stack.last_mut().unwrap());
+                add_value(&mut chars, stack.last_mut().ok_or("unmatched ]")?)?;
                 let vec = stack.pop().unwrap();
                 let item = Item::List { vec };
                 if stack.is_empty() {
@@ -70,21 +112,22 @@ fn parse_line(line: String) -> Option<Item> {
                 }
             },
             ',' => {
-                add_value(&mut chars, stack.last_mut().unwrap());
+                add_value(&mut chars, stack.last_mut().ok_or(", outside of a list")?)?;
             },
             _ => chars.push(c),
         }
     }
-    result
+    result.ok_or_else(|| "expected a packet, e.g. [1,[2,3]]".into())
 }
 
-fn add_value(chars: &mut String, vec: &mut Vec<Item>) {
+fn add_value(chars: &mut String, vec: &mut Vec<Item>) -> Result<(), ParseIntError> {
     if !chars.is_empty() {
-        let val: Value = chars.parse().unwrap();
+        let val: Value = chars.parse()?;
         chars.clear();
         let item = Item::Val { val };
         vec.push(item);
     }
+    Ok(())
 }
 
 struct State {
@@ -96,26 +139,18 @@ struct State {
 impl State {
     fn new() -> State {
         State {
-            items: Vec::with_capacity(2),
+            items: Vec::new(),
             current_index: 0,
             sum_correct_order_indices: 0,
         }
     }
 }
 
-fn progress_next(state: &mut State) {
-    state.current_index += 1;
-    if in_right_order(&state.items) {
-        state.sum_correct_order_indices += state.current_index;
-    }
-    state.items.clear();
-}
-
-fn in_right_order(items: &Vec<Item>) -> bool {
+fn in_right_order(items: &[Item]) -> bool {
     if items.len() != 2 {
         panic!("Unexpected items length: {}", items.len());
     }
-    let left = items.get(0).unwrap();
+    let left = items.first().unwrap();
     let right = items.get(1).unwrap();
     compare_left_and_right(left, right) == Ordering::Less
 }
@@ -124,7 +159,7 @@ fn in_right_order(items: &Vec<Item>) -> bool {
 fn compare_left_and_right(left: &Item, right: &Item) -> Ordering {
     match (left, right) {
         (Item::Val { val: left_val }, Item::Val{ val: right_val }) => {
-            left_val.cmp(&right_val)
+            left_val.cmp(right_val)
         },
         (Item::List { vec: _vec }, Item::Val { val }) => {
             compare_left_and_right(left, &Item::List { vec: vec!(Item::Val{ val: *val }) })
@@ -154,32 +189,20 @@ fn compare_left_and_right(left: &Item, right: &Item) -> Ordering {
     }
 }
 
-fn accumulate1(mut state: State, opt_item: Option<Item>) -> State {
-    match opt_item {
-        Some(item) => {
-            state.items.push(item);
-        },
-        None => {
-            progress_next(&mut state);
-        },
+fn accumulate1(mut state: State, pair: Vec<Item>) -> State {
+    state.current_index += 1;
+    if in_right_order(&pair) {
+        state.sum_correct_order_indices += state.current_index;
     }
     state
 }
 
-fn reduce1(mut state: State) -> usize {
-    if !state.items.is_empty() {
-        progress_next(&mut state);
-    }
+fn reduce1(state: State) -> usize {
     state.sum_correct_order_indices
 }
 
-fn accumulate2(mut state: State, opt_item: Option<Item>) -> State {
-    match opt_item {
-        Some(item) => {
-            state.items.push(item);
-        },
-        None => {}
-    }
+fn accumulate2(mut state: State, pair: Vec<Item>) -> State {
+    state.items.extend(pair);
     state
 }
 
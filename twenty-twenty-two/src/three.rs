@@ -1,13 +1,11 @@
+use crate::error::Error;
 use crate::utils;
-use std::io::Error;
 use std::collections::HashSet;
 use std::convert::identity;
 
-
-pub fn _3a() -> Result<u64, Error> {
-    let filename = "3/input.txt";
+pub fn _3a(input: utils::Input) -> Result<u64, Error> {
     utils::process_file(
-        filename,
+        input,
         parse_line1,
         0,
         accumulate1,
@@ -15,10 +13,9 @@ pub fn _3a() -> Result<u64, Error> {
     )
 }
 
-pub fn _3b() -> Result<u64, Error> {
-    let filename = "3/input.txt";
+pub fn _3b(input: utils::Input) -> Result<u64, Error> {
     utils::process_file(
-        filename,
+        input,
         identity,
         GroupDetails::new(HashSet::new(), 0, 0),
         accumulate2,
@@ -35,13 +32,7 @@ fn parse_line1(line: String) -> Option<char> {
         set
     };
     let first_items: HashSet<char> = first_half.chars().fold(HashSet::new(), collector);
-    for (_, c) in second_half.chars().enumerate() {
-        if first_items.contains(&c) {
-            //we found it... can exit now
-            return Some(c);
-        }
-    }
-    None
+    second_half.chars().find(|&c| first_items.contains(&c))
 }
 
 fn accumulate1(sum: u64, item_opt: Option<char>) -> u64 {
@@ -84,7 +75,7 @@ fn accumulate2(group_details: GroupDetails, line: String) -> GroupDetails {
         1 => {
             //second line - candidate set should be the intersection of the candidates and these chars so keep if we find in both
             let mut new_candidates = HashSet::new();
-            for (_, c) in line.chars().enumerate() {
+            for c in line.chars() {
                 if group_details.candidate_items.contains(&c) {
                     new_candidates.insert(c);
                 }
@@ -93,7 +84,7 @@ fn accumulate2(group_details: GroupDetails, line: String) -> GroupDetails {
         },
         2 => {
             //third line - try to find the one that is matching - we'll assume there is going to be only 1
-            for (_, c) in line.chars().enumerate() {
+            for c in line.chars() {
                 if group_details.candidate_items.contains(&c) {
                     let item_priority = get_item_value(c);
                     return GroupDetails::new(HashSet::new(), 0, group_details.sum + item_priority)
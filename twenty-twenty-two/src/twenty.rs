@@ -1,27 +1,30 @@
 use std::collections::HashSet;
 use std::fmt::Display;
 use std::cmp;
+use std::num::ParseIntError;
 
+use crate::error::Error;
 use crate::utils;
 
-const FILE_NAME: &str = "20/input.txt";
-// const FILE_NAME: &str = "20/test_input.txt";
-// const FILE_NAME: &str = "20/my_test_input.txt";
+pub fn _20a(input: utils::Input) -> Result<i64, Error> {
+    utils::try_process_file(input, parse_line, State::new(), accumulate1, reduce1)
+}
 
-pub fn _20a() -> Result<i64, std::io::Error> {
-    utils::process_file(FILE_NAME, parse_line, State::new(), accumulate1, reduce1)
+pub fn _20b(input: utils::Input) -> Result<i64, Error> {
+    utils::try_process_file(input, parse_line, State::new(), accumulate2, reduce2)
 }
 
-pub fn _20b() -> Result<i64, std::io::Error> {
-    utils::process_file(FILE_NAME, parse_line, State::new(), accumulate2, reduce2)
+///Read the numbers with the decryption key applied, for benchmarking [process2]
+pub fn read_decrypted_numbers(input: utils::Input) -> Result<Vec<Number>, Error> {
+    utils::try_process_file(input, parse_line, State::new(), accumulate2, |state| state.numbers)
 }
 
-fn parse_line(line: String) -> i64 {
-    line.parse().unwrap()
+fn parse_line(line: &str) -> Result<i64, ParseIntError> {
+    line.parse()
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct Number {
+pub struct Number {
     number: i64,
     original_position: usize,
 }
@@ -79,14 +82,14 @@ fn reduce2(mut state: State) -> i64 {
     let numbers = &mut state.numbers;
 
     for iteration in 0..TIMES_TO_DECRYPT {
-        println!("Processing iteration {}", iteration);
+        eprintln!("Processing iteration {}", iteration);
         process2(&original_numbers, numbers);
     }
 
     calculate_final_value(numbers)
 }
 
-fn calculate_final_value(numbers: &Vec<Number>) -> i64 {
+fn calculate_final_value(numbers: &[Number]) -> i64 {
     let zero_index = find_zero(numbers).unwrap();
 
     let index_1000 = (zero_index + 1000) % numbers.len();
@@ -97,7 +100,7 @@ fn calculate_final_value(numbers: &Vec<Number>) -> i64 {
     let value_2000 = numbers.get(index_2000).unwrap().number;
     let value_3000 = numbers.get(index_3000).unwrap().number;
 
-    println!("{} + {} + {}", value_1000, value_2000, value_3000);
+    eprintln!("{} + {} + {}", value_1000, value_2000, value_3000);
     value_1000 + value_2000 + value_3000
 }
 
@@ -110,11 +113,11 @@ fn process(numbers: &mut Vec<Number>) {
     while num_processed < numbers.len() && current_index < numbers.len() {
         let num = {
             let number = numbers.get(current_index).unwrap();
-            if already_processed.contains(&number) {
+            if already_processed.contains(number) {
                 current_index += 1;
                 continue;
             }
-            already_processed.insert(number.clone());
+            already_processed.insert(*number);
             number.number
         };
         shift_number_at(numbers, &mut current_index, len, num);
@@ -122,7 +125,8 @@ fn process(numbers: &mut Vec<Number>) {
     }
 }
 
-fn process2(original_numbers: &Vec<Number>, numbers: &mut Vec<Number>) {
+///Mix the numbers once, moving them in their original order
+pub fn process2(original_numbers: &[Number], numbers: &mut Vec<Number>) {
     let len = numbers.len();
 
     for number in original_numbers {
@@ -182,7 +186,7 @@ fn calculate_new_index(current_index: usize, len: usize, num: i64) -> usize {
     pos
 }
 
-fn find_zero(numbers: &Vec<Number>) -> Option<usize> {
+fn find_zero(numbers: &[Number]) -> Option<usize> {
     for (index, value) in numbers.iter().enumerate() {
         if value.number == 0 {
             return Some(index);
@@ -191,7 +195,7 @@ fn find_zero(numbers: &Vec<Number>) -> Option<usize> {
     None
 }
 
-fn find_index_of(number_to_look_for: &Number, numbers: &Vec<Number>) -> Option<usize> {
+fn find_index_of(number_to_look_for: &Number, numbers: &[Number]) -> Option<usize> {
     for (index, number) in numbers.iter().enumerate() {
         if number_to_look_for == number {
             return Some(index);
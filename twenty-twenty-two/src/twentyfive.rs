@@ -1,17 +1,15 @@
 use std::collections::VecDeque;
 use std::fmt::Display;
 
+use crate::error::{Error, ParseResult};
 use crate::utils;
 
-const FILE_NAME: &str = "25/input.txt";
-//const FILE_NAME: &str = "25/test_input.txt";
-//const FILE_NAME: &str = "25/my_test_input.txt";
-
-pub fn _25a() -> Result<String, std::io::Error> {
-    utils::process_file(FILE_NAME, parse_line, State::new(), accumulate, reduce)
+pub fn _25a(input: utils::Input) -> Result<String, Error> {
+    utils::try_process_file(input, parse_line, State::new(), accumulate, reduce)
 }
 
-enum SnafuDigit {
+///A digit of a SNAFU number, which is base 5 with digits running from -2 to 2
+pub enum SnafuDigit {
     Two,
     One,
     Zero,
@@ -32,21 +30,29 @@ impl Display for SnafuDigit {
     }
 }
 
-fn parse_line(line: String) -> Vec<SnafuDigit> {
+///The value of a SNAFU number, e.g. "1=-0-2" is 1747
+pub fn from_snafu(snafu: &str) -> ParseResult<i128> {
+    parse_line(snafu).map(|digits| convert_into_native(&digits))
+}
+
+///The SNAFU number for the value, e.g. 1747 is "1=-0-2"
+pub fn to_snafu(value: i128) -> String {
+    let mut result = String::new();
+    utils::output_into_iter(&mut result, "", &mut convert_into_snafu(value).iter());
+    result
+}
+
+fn parse_line(line: &str) -> ParseResult<Vec<SnafuDigit>> {
     line.chars()
-        .into_iter()
         .map(|c| match c {
-            '2' => SnafuDigit::Two,
-            '1' => SnafuDigit::One,
-            '0' => SnafuDigit::Zero,
-            '-' => SnafuDigit::Minus,
-            '=' => SnafuDigit::DoubleMinus,
-            _ => panic!("Unrecognised snafu digit: {}", c),
-        })
-        .fold(Vec::with_capacity(line.len()), |mut digits, d| {
-            digits.push(d);
-            digits
+            '2' => Ok(SnafuDigit::Two),
+            '1' => Ok(SnafuDigit::One),
+            '0' => Ok(SnafuDigit::Zero),
+            '-' => Ok(SnafuDigit::Minus),
+            '=' => Ok(SnafuDigit::DoubleMinus),
+            _ => Err(format!("Unrecognised snafu digit: {}", c).into()),
         })
+        .collect()
 }
 
 struct Number {
@@ -84,8 +90,8 @@ impl State {
 }
 
 fn accumulate(mut state: State, digits: Vec<SnafuDigit>) -> State {
-    // utils::output_into_iter_io(std::io::stdout(), "", &mut digits.iter());
-    // println!();
+    // utils::output_into_iter_io(std::io::stderr(), "", &mut digits.iter());
+    // eprintln!();
     let value = convert_into_native(&digits);
     let number = Number::new(value, digits);
     state.numbers.push(number);
@@ -94,30 +100,27 @@ fn accumulate(mut state: State, digits: Vec<SnafuDigit>) -> State {
 
 fn reduce(state: State) -> String {
     //Output
-    // println!();
-    // utils::output_into_iter_io(std::io::stdout(), "\n", &mut state.numbers.iter());
-    // println!();
-    // println!();
+    // eprintln!();
+    // utils::output_into_iter_io(std::io::stderr(), "\n", &mut state.numbers.iter());
+    // eprintln!();
+    // eprintln!();
 
     //Output
     // for num in state.numbers.iter() {
     //     let converted_back = convert_into_snafu(num.value);
-    //     utils::output_into_iter_io(std::io::stdout(), "", &mut converted_back.iter());
-    //     println!();
+    //     utils::output_into_iter_io(std::io::stderr(), "", &mut converted_back.iter());
+    //     eprintln!();
     // }
-    // println!();
-    // println!();
+    // eprintln!();
+    // eprintln!();
 
     let sum: i128 = state.numbers.iter().map(|num| num.value).sum();
-    let snafu_digits = convert_into_snafu(sum);
-    let mut result = String::new();
-    utils::output_into_iter(&mut result, "", &mut snafu_digits.iter());
-    result
+    to_snafu(sum)
 }
 
 const BASE: i128 = 5;
 
-fn convert_into_native(snafu_digits: &Vec<SnafuDigit>) -> i128 {
+pub fn convert_into_native(snafu_digits: &[SnafuDigit]) -> i128 {
     let mut value = 0;
     for (place, digit) in snafu_digits.iter().rev().enumerate() {
         let multiplier = BASE.pow(place as u32);
@@ -125,15 +128,15 @@ fn convert_into_native(snafu_digits: &Vec<SnafuDigit>) -> i128 {
             SnafuDigit::Two => multiplier * 2,
             SnafuDigit::One => multiplier,
             SnafuDigit::Zero => 0,
-            SnafuDigit::Minus => multiplier * -1,
+            SnafuDigit::Minus => -multiplier,
             SnafuDigit::DoubleMinus => multiplier * -2,
         };
     }
     value
 }
 
-fn convert_into_snafu(value: i128) -> Vec<SnafuDigit> {
-    // println!("=== Value: {}", value);
+pub fn convert_into_snafu(value: i128) -> Vec<SnafuDigit> {
+    // eprintln!("=== Value: {}", value);
     //Special zero case handling
     if value == 0 {
         return vec![SnafuDigit::Zero];
@@ -143,17 +146,17 @@ fn convert_into_snafu(value: i128) -> Vec<SnafuDigit> {
     let mut previous_was_adjustment = false;
     while remaining != 0 {
         let mut remainder = remaining.rem_euclid(BASE);
-        // println!("remaining: {}", remaining);
-        // println!("remainder: {}", remainder);
+        // eprintln!("remaining: {}", remaining);
+        // eprintln!("remainder: {}", remainder);
         if remainder > 2 {
             remainder -= 5;
-            // println!("remainder*: {}", remainder);
+            // eprintln!("remainder*: {}", remainder);
         }
 
         if remainder == 0 {
             if !previous_was_adjustment {
                 result.push_front(SnafuDigit::Zero);
-                // println!("digit: {}", SnafuDigit::Zero);
+                // eprintln!("digit: {}", SnafuDigit::Zero);
             }
             remaining /= BASE;
             previous_was_adjustment = false;
@@ -165,7 +168,7 @@ fn convert_into_snafu(value: i128) -> Vec<SnafuDigit> {
                     remainder, value
                 );
             }
-            // println!("digit: {}", digit.as_ref().unwrap());
+            // eprintln!("digit: {}", digit.as_ref().unwrap());
             result.push_front(digit.unwrap());
             remaining -= remainder;
             previous_was_adjustment = true;
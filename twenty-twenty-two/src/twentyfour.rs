@@ -1,19 +1,23 @@
-use std::collections::HashSet;
-use std::mem::swap;
-
+use crate::direction::Direction4;
+use crate::error::Error;
+use crate::grid::{self, Bounds, DenseGrid, Grid, Pos, Scale, Wrap};
+use crate::search;
 use crate::utils;
 
-const FILE_NAME: &str = "24/input.txt";
-//const FILE_NAME: &str = "24/test_input.txt";
+pub fn _24a(input: utils::Input) -> Result<usize, Error> {
+    utils::try_process_file(input, grid::row_parser(parse_square), State::new(), accumulate, reduce1)
+}
 
-pub fn _24a() -> Result<usize, std::io::Error> {
-    utils::process_file(FILE_NAME, parse_line, State::new(), accumulate, reduce1)
+pub fn _24b(input: utils::Input) -> Result<usize, Error> {
+    utils::try_process_file(input, grid::row_parser(parse_square), State::new(), accumulate, reduce2)
 }
 
-pub fn _24b() -> Result<usize, std::io::Error> {
-    utils::process_file(FILE_NAME, parse_line, State::new(), accumulate, reduce2)
+///Read the valley without crossing it, for benchmarking [reduce]
+pub fn read_valley(input: utils::Input) -> Result<State, Error> {
+    utils::try_process_file(input, grid::row_parser(parse_square), State::new(), accumulate, |state| state)
 }
 
+#[derive(Clone)]
 struct Square {
     wall: bool,
     north_wind: bool,
@@ -55,6 +59,24 @@ impl Square {
         self.wall || self.north_wind || self.east_wind || self.south_wind || self.west_wind
     }
 
+    fn wind_mut(&mut self, direction: Direction4) -> &mut bool {
+        match direction {
+            Direction4::North => &mut self.north_wind,
+            Direction4::East => &mut self.east_wind,
+            Direction4::South => &mut self.south_wind,
+            Direction4::West => &mut self.west_wind,
+        }
+    }
+
+    fn wind(&self, direction: Direction4) -> bool {
+        match direction {
+            Direction4::North => self.north_wind,
+            Direction4::East => self.east_wind,
+            Direction4::South => self.south_wind,
+            Direction4::West => self.west_wind,
+        }
+    }
+
     fn clear_wind(&mut self) {
         self.north_wind = false;
         self.east_wind = false;
@@ -63,70 +85,33 @@ impl Square {
     }
 }
 
-fn parse_line(line: String) -> Vec<Square> {
-    let mut row: Vec<Square> = Vec::with_capacity(line.len());
-    for c in line.chars() {
-        let square = match c {
-            '#' => Square::new_wall(),
-            '^' => Square::new_north_wind(),
-            '>' => Square::new_east_wind(),
-            'v' => Square::new_south_wind(),
-            '<' => Square::new_west_wind(),
-            '.' => Square::new_empty(),
-            _ => panic!("Unrecognised square '{}'", c),
-        };
-        row.push(square);
+fn parse_square(c: char) -> Result<Square, String> {
+    match c {
+        '#' => Ok(Square::new_wall()),
+        '^' => Ok(Square::new_north_wind()),
+        '>' => Ok(Square::new_east_wind()),
+        'v' => Ok(Square::new_south_wind()),
+        '<' => Ok(Square::new_west_wind()),
+        '.' => Ok(Square::new_empty()),
+        _ => Err(format!("Unrecognised square '{}'", c)),
     }
-    row
 }
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct Coord {
-    x: usize,
-    y: usize,
-}
-
-impl Coord {
-    pub fn new(x: usize, y: usize) -> Coord {
-        Coord { x, y }
-    }
-}
-
-struct State {
-    num_cols: usize,
-    num_rows: usize,
-    rows: Vec<Vec<Square>>,
-    next_rows: Vec<Vec<Square>>,
+#[derive(Clone)]
+pub struct State {
+    rows: DenseGrid<Square>,
 }
 
 impl State {
-    pub fn new() -> State {
+    pub(crate) fn new() -> State {
         State {
-            num_cols: 0,
-            num_rows: 0,
-            rows: Vec::new(),
-            next_rows: Vec::new(),
+            rows: DenseGrid::new(),
         }
     }
-
-    fn get_square(&self, x: usize, y: usize) -> &Square {
-        self.rows.get(y).unwrap().get(x).unwrap()
-    }
 }
 
 fn accumulate(mut state: State, row: Vec<Square>) -> State {
-    state.num_cols = row.len();
-    let mut next_rows_row = Vec::with_capacity(state.num_cols);
-    for square in row.iter() {
-        next_rows_row.push(if square.wall {
-            Square::new_wall()
-        } else {
-            Square::new_empty()
-        });
-    }
-    state.next_rows.push(next_rows_row);
-    state.rows.push(row);
-    state.num_rows += 1;
+    state.rows.push_row(row);
     state
 }
 
@@ -138,211 +123,76 @@ fn reduce1(mut state: State) -> usize {
 fn reduce2(mut state: State) -> usize {
     let (start, goal) = get_start_and_goal(&state);
     let trip_out = reduce(&mut state, start, goal);
-    println!("Out: {}", trip_out);
+    eprintln!("Out: {}", trip_out);
     let return_trip = reduce(&mut state, goal, start);
-    println!("Back: {}", return_trip);
+    eprintln!("Back: {}", return_trip);
     let back_again = reduce(&mut state, start, goal);
-    println!("Out Again: {}", back_again);
+    eprintln!("Out Again: {}", back_again);
     trip_out + return_trip + back_again
 }
 
-fn get_start_and_goal(state: &State) -> (Coord, Coord) {
-    let start_pos = Coord::new(1, 0);
-    let mut goal_opt: Option<Coord> = None;
-    for x in 0..(state.num_cols) {
-        let square = state.get_square(x, state.num_rows - 1);
-        if square.is_empty() {
-            goal_opt = Some(Coord::new(x, state.num_rows - 1));
-            break;
-        }
-    }
-    let goal = goal_opt.expect("Didn't find the goal");
+pub fn get_start_and_goal(state: &State) -> (Pos, Pos) {
+    let start_pos = Pos::new(1, 0);
+    let bottom = state.rows.height() as Scale - 1;
+    let goal = state.rows.bounds().columns()
+        .map(|x| Pos::new(x, bottom))
+        .find(|pos| state.rows[*pos].is_empty())
+        .expect("Didn't find the goal");
     (start_pos, goal)
 }
 
-fn reduce(state: &mut State, start_pos: Coord, goal: Coord) -> usize {
+///The number of minutes to get from start_pos to goal, the winds are left as they were on arrival
+pub fn reduce(state: &mut State, start_pos: Pos, goal: Pos) -> usize {
     //Output
-    output_state(&state, Some(&start_pos));
-
-    let current_positions: &mut HashSet<Coord> = &mut HashSet::new();
-    current_positions.insert(start_pos);
-    let new_positions: &mut HashSet<Coord> = &mut HashSet::new();
-
-    let mut steps_required: Option<usize> = None;
-
-    'steps: for step in 0..usize::MAX {
-        move_wind(state);
-
-        for current_position in current_positions.drain() {
-            let mut choices = Vec::with_capacity(5);
-            //choices are north, east, south, west or wait
-            if current_position.y > 0 {
-                choices.push(Coord::new(current_position.x, current_position.y - 1)); //North
-            }
-            if current_position.x < state.num_cols - 1 {
-                choices.push(Coord::new(current_position.x + 1, current_position.y)); //East
+    output_state(state, Some(&start_pos));
+
+    //the valley at each minute, worked out as the search gets to it
+    let mut valleys = vec![state.rows.clone()];
+    let route = search::astar(
+        (start_pos, 0),
+        |(position, minute): &(Pos, usize)| {
+            while valleys.len() <= minute + 1 {
+                let next_valley = move_wind(valleys.last().unwrap());
+                valleys.push(next_valley);
             }
-            if current_position.y < state.num_rows - 1 {
-                choices.push(Coord::new(current_position.x, current_position.y + 1)); //South
-            }
-            if current_position.x > 0 {
-                choices.push(Coord::new(current_position.x - 1, current_position.y)); //West
-            }
-            choices.push(Coord::new(current_position.x, current_position.y)); //Wait
-
-            'choices: for choice in choices {
-                if choice == start_pos && current_position != start_pos {
-                    //no point in going back to the start
-                    continue 'choices;
-                }
-                if choice == goal {
-                    steps_required = Some(step);
-                    break 'steps;
-                }
-                let choice_square = state.get_square(choice.x, choice.y);
-                if choice_square.is_empty() {
-                    //try this square next!!
-                    new_positions.insert(choice);
-                }
-            }
-        }
-
-        if new_positions.is_empty() {
-            break;
-        }
-
-        swap(current_positions, new_positions);
-
-        if step % 10000 == 0 && step > 0 {
-            println!("Step {} Completed", step);
-        }
-    }
-
-    steps_required.map(|steps| steps + 1).expect("Didn't find a route")
-}
-
-fn move_wind(state: &mut State) {
-    let (num_rows, num_cols) = (state.num_rows, state.num_cols);
-    //first (ignoring walls rows and columns) clear the next_rows,
-    //these will get updated with the new values and then swapped with the rows
-    for y in 1..(num_rows - 1) {
-        for x in 1..(num_cols - 1) {
-            state.next_rows.get_mut(y).unwrap().get_mut(x).unwrap().clear_wind();
-        }
+            let valley = &valleys[minute + 1];
+            //choices are north, east, south, west or wait
+            state.rows.neighbours4(*position, Wrap::None)
+                .chain([*position])
+                .filter(|choice| valley[*choice].is_empty())
+                .map(|choice| ((choice, minute + 1), 1))
+                .collect::<Vec<_>>()
+        },
+        |(position, _)| position.manhattan(goal) as usize,
+        |(position, _)| *position == goal,
+    ).expect("Didn't find a route");
+    eprintln!("Found a route after looking at {} positions", route.stats.expanded);
+
+    state.rows = valleys.swap_remove(route.cost);
+    route.cost
+}
+
+///The valley a minute after rows
+fn move_wind(rows: &DenseGrid<Square>) -> DenseGrid<Square> {
+    //the winds blow around inside the walls
+    let bounds = rows.bounds();
+    let inside = Bounds::new(bounds.min_x + 1, bounds.min_y + 1, bounds.max_x - 1, bounds.max_y - 1);
+    //first clear the winds from the next rows, these will get updated with the new values
+    let mut next_rows = rows.clone();
+    for pos in inside.positions() {
+        next_rows[pos].clear_wind();
     }
     //ignoring the walls, move the winds
-    for y in 1..(num_rows - 1) {
-        for x in 1..(num_cols - 1) {
-            let square = state.rows.get(y).unwrap().get(x).unwrap();
-            move_north_wind(&mut state.next_rows, square, num_rows, x, y);
-            move_east_wind(&mut state.next_rows, square, num_cols, x, y);
-            move_south_wind(&mut state.next_rows, square, num_rows, x, y);
-            move_west_wind(&mut state.next_rows, square, num_cols, x, y);
-        }
-    }
-    //swap over ready for further processing
-    swap(&mut state.next_rows, &mut state.rows);
-}
-
-fn move_north_wind(next_rows: &mut Vec<Vec<Square>>, square: &Square, num_rows: usize, x: usize, y: usize) {
-    if !square.north_wind {
-        return;
-    }
-    let next_square = if y == 1 {
-        //wrap around
-        next_rows.get_mut(num_rows - 2).unwrap().get_mut(x).unwrap()
-    } else {
-        next_rows.get_mut(y - 1).unwrap().get_mut(x).unwrap()
-    };
-    next_square.north_wind = true;
-}
-
-fn move_east_wind(next_rows: &mut Vec<Vec<Square>>, square: &Square, num_cols: usize, x: usize, y: usize) {
-    if !square.east_wind {
-        return;
-    }
-    let next_square = if x >= num_cols - 2 {
-        //wrap around
-        next_rows.get_mut(y).unwrap().get_mut(1).unwrap()
-    } else {
-        next_rows.get_mut(y).unwrap().get_mut(x + 1).unwrap()
-    };
-    next_square.east_wind = true;
-}
-
-fn move_south_wind(next_rows: &mut Vec<Vec<Square>>, square: &Square, num_rows: usize, x: usize, y: usize) {
-    if !square.south_wind {
-        return;
-    }
-    let next_square = if y >= num_rows - 2 {
-        //wrap around
-        next_rows.get_mut(1).unwrap().get_mut(x).unwrap()
-    } else {
-        next_rows.get_mut(y + 1).unwrap().get_mut(x).unwrap()
-    };
-    next_square.south_wind = true;
-}
-
-fn move_west_wind(next_rows: &mut Vec<Vec<Square>>, square: &Square, num_cols: usize, x: usize, y: usize) {
-    if !square.west_wind {
-        return;
-    }
-    let next_square = if x == 1 {
-        //wrap around
-        next_rows.get_mut(y).unwrap().get_mut(num_cols - 2).unwrap()
-    } else {
-        next_rows.get_mut(y).unwrap().get_mut(x - 1).unwrap()
-    };
-    next_square.west_wind = true;
-}
-
-fn output_state(state: &State, elf_position: Option<&Coord>) {
-    println!();
-    println!("{} x {}", state.num_cols, state.num_rows);
-    for y in 0..state.num_rows {
-        'x_loop: for x in 0..state.num_cols {
-            let position = Coord::new(x, y);
-            if elf_position.is_some_and(|ep| *ep == position) {
-                print!("E");
-                continue 'x_loop;
-            }
-            output_square(state.get_square(x, y));
+    for pos in inside.positions() {
+        let square = &rows[pos];
+        for direction in Direction4::ALL.into_iter().filter(|direction| square.wind(*direction)) {
+            *next_rows[inside.wrap(pos.step(direction))].wind_mut(direction) = true;
         }
-        println!();
     }
-    println!();
+    next_rows
 }
 
-fn output_square(square: &Square) {
-    if square.wall {
-        print!("#");
-        return;
-    }
-    let mut winds = 0;
-    let mut last_wind: Option<&str> = None;
-    if square.north_wind {
-        winds += 1;
-        last_wind = Some("^");
-    }
-    if square.east_wind {
-        winds += 1;
-        last_wind = Some(">");
-    }
-    if square.south_wind {
-        winds += 1;
-        last_wind = Some("v");
-    }
-    if square.west_wind {
-        winds += 1;
-        last_wind = Some("<");
-    }
-    if winds == 0 {
-        print!(".");
-        return
-    }
-    if winds == 1 {
-        print!("{}", last_wind.expect("Missing wind"));
-        return;
-    }
-    print!("{}", winds);
+fn output_state(state: &State, elf_position: Option<&Pos>) {
+    let from = elf_position.map_or(String::from("-"), |pos| pos.to_string());
+    eprintln!("Valley {} x {}, setting off from {}", state.rows.width(), state.rows.height(), from);
 }
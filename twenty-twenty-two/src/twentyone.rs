@@ -5,6 +5,7 @@ use std::rc::Rc;
 use lazy_static::lazy_static;
 use regex::Regex;
 
+use crate::error::{Error, ParseResult};
 use crate::utils;
 
 type MonkeyID = String;
@@ -13,15 +14,12 @@ type Val = f64;
 const ROOT: &str = "root";
 const HUMAN: &str = "humn";
 
-const FILE_NAME: &str = "21/input.txt";
-// const FILE_NAME: &str = "21/test_input.txt";
-
-pub fn _21a() -> Result<Val, std::io::Error> {
-    utils::process_file(FILE_NAME, parse_line, State::new(), accumulate, reduce1)
+pub fn _21a(input: utils::Input) -> Result<Val, Error> {
+    utils::try_process_file(input, parse_line, State::new(), accumulate, reduce1)
 }
 
-pub fn _21b() -> Result<Val, std::io::Error> {
-    utils::process_file(FILE_NAME, parse_line, State::new(), accumulate, reduce2)
+pub fn _21b(input: utils::Input) -> Result<Val, Error> {
+    utils::try_process_file(input, parse_line, State::new(), accumulate, reduce2)
 }
 
 enum Operation {
@@ -102,28 +100,28 @@ impl Display for Monkey {
     }
 }
 
-fn parse_line(line: String) -> Monkey {
+fn parse_line(line: &str) -> ParseResult<Monkey> {
     lazy_static! {
         static ref VAL: Regex = Regex::new(r"^(\w+): (\d+)$").unwrap();
         static ref OP: Regex = Regex::new(r"^(\w+): (\w+) (\+|\-|\*|/) (\w+)$").unwrap();
     }
     None.or_else(|| {
-        VAL.captures(&line).map(|caps| {
-            let id = String::from(caps.get(1).unwrap().as_str());
-            let value: Val = caps.get(2).unwrap().as_str().parse().unwrap();
-            Monkey::new_value(id, value)
+        VAL.captures(line).map(|caps| {
+            let id = String::from(&caps[1]);
+            let value: Val = caps[2].parse()?;
+            Ok(Monkey::new_value(id, value))
         })
     })
     .or_else(|| {
-        OP.captures(&line).map(|caps| {
-            let id = String::from(caps.get(1).unwrap().as_str());
-            let ref1 = String::from(caps.get(2).unwrap().as_str());
-            let op = parse_operation(caps.get(3).unwrap().as_str()).expect(line.as_str());
-            let ref2 = String::from(caps.get(4).unwrap().as_str());
-            Monkey::new_op(id, ref1, op, ref2)
+        OP.captures(line).map(|caps| {
+            let id = String::from(&caps[1]);
+            let ref1 = String::from(&caps[2]);
+            let op = parse_operation(&caps[3]).ok_or("unrecognised operation")?;
+            let ref2 = String::from(&caps[4]);
+            Ok(Monkey::new_op(id, ref1, op, ref2))
         })
     })
-    .expect(line.as_str())
+    .unwrap_or_else(|| Err("expected <id>: <number> or <id>: <id> <op> <id>".into()))
 }
 
 fn parse_operation(op: &str) -> Option<Operation> {
@@ -157,24 +155,21 @@ impl State {
 }
 
 fn accumulate(mut state: State, monkey: Monkey) -> State {
-    // println!("{}", monkey);
+    // eprintln!("{}", monkey);
     if let Some(value) = do_monkey_calc(&state.monkey_values, &monkey.calc) {
         state.monkey_values.insert(Rc::clone(&monkey.id), value);
     }
-    match &monkey.calc {
-        MonkeyCalc::Op {
+    if let MonkeyCalc::Op {
             ref1,
             op: _op,
             ref2,
-        } => {
-            state
-                .monkey_back_refs
-                .insert(Rc::clone(ref1), Rc::clone(&monkey.id));
-            state
-                .monkey_back_refs
-                .insert(Rc::clone(ref2), Rc::clone(&monkey.id));
-        }
-        _ => {}
+        } = &monkey.calc {
+        state
+            .monkey_back_refs
+            .insert(Rc::clone(ref1), Rc::clone(&monkey.id));
+        state
+            .monkey_back_refs
+            .insert(Rc::clone(ref2), Rc::clone(&monkey.id));
     }
     let monkey_rc = Rc::new(monkey);
     state
@@ -190,18 +185,18 @@ fn reduce1(mut state: State) -> Val {
 
 fn reduce(state: &mut State) -> Val {
     //Output
-    // println!();
+    // eprintln!();
     //repeatedly loop until we can calculate the root
     let mut num_loops = 0;
     let root_value: Option<Val>;
     'outer: loop {
         //Output
-        // utils::output_into_iter_io(std::io::stdout(), "\n", &mut state.monkeys.iter());
-        // println!();
+        // utils::output_into_iter_io(std::io::stderr(), "\n", &mut state.monkeys.iter());
+        // eprintln!();
 
         num_loops += 1;
         'inner: for monkey in state.monkeys.iter_mut() {
-            if state.monkey_values.get(&monkey.id).is_some() {
+            if state.monkey_values.contains_key(&monkey.id) {
                 continue 'inner;
             }
             let monkey_value = do_monkey_calc(&state.monkey_values, &monkey.calc);
@@ -218,11 +213,11 @@ fn reduce(state: &mut State) -> Val {
     }
 
     //Output
-    // println!();
-    // utils::output_into_iter_io(std::io::stdout(), "\n", &mut state.monkeys.iter());
-    // println!();
+    // eprintln!();
+    // utils::output_into_iter_io(std::io::stderr(), "\n", &mut state.monkeys.iter());
+    // eprintln!();
 
-    println!("Completed in {} loops", num_loops);
+    eprintln!("Completed in {} loops", num_loops);
     root_value.unwrap()
 }
 
@@ -230,15 +225,9 @@ fn do_monkey_calc(monkey_values: &HashMap<Rc<MonkeyID>, Val>, calc: &MonkeyCalc)
     match calc {
         MonkeyCalc::Op { ref1, op, ref2 } => {
             let opt_value1 = monkey_values.get(ref1);
-            let value1 = match opt_value1 {
-                Some(value) => value,
-                None => return None,
-            };
+            let value1 = opt_value1?;
             let opt_value2 = monkey_values.get(ref2);
-            let value2 = match opt_value2 {
-                Some(value) => value,
-                None => return None,
-            };
+            let value2 = opt_value2?;
             Some(do_calc(value1, op, value2))
         }
         MonkeyCalc::Value { value } => Some(*value),
@@ -290,10 +279,10 @@ fn reduce2(mut state: State) -> Val {
             //decide which side to traverse
             if monkeys_in_path.contains(ref1) {
                 let expected_value = state.monkey_values.get(ref2).unwrap();
-                (Rc::clone(&ref1), *expected_value)
+                (Rc::clone(ref1), *expected_value)
             } else {
                 let expected_value = state.monkey_values.get(ref1).unwrap();
-                (Rc::clone(&ref2), *expected_value)
+                (Rc::clone(ref2), *expected_value)
             }
         }
         MonkeyCalc::Value { value: _value } => panic!("Not expecting route to be a value"),
@@ -308,15 +297,15 @@ fn reduce2(mut state: State) -> Val {
                 let (next, undo_op, correct_value) = if monkeys_in_path.contains(ref1) {
                     let correct_value = state.monkey_values.get(ref2).unwrap();
                     (
-                        Rc::clone(&ref1),
-                        deduce_undo_op(&op, Side::Left),
+                        Rc::clone(ref1),
+                        deduce_undo_op(op, Side::Left),
                         *correct_value,
                     )
                 } else {
                     let correct_value = state.monkey_values.get(ref1).unwrap();
                     (
-                        Rc::clone(&ref2),
-                        deduce_undo_op(&op, Side::Right),
+                        Rc::clone(ref2),
+                        deduce_undo_op(op, Side::Right),
                         *correct_value,
                     )
                 };
@@ -341,17 +330,17 @@ fn get_monkeys_in_root_to_human(
     root: &Rc<MonkeyID>,
     human: &Rc<MonkeyID>,
 ) -> HashSet<Rc<MonkeyID>> {
-    let mut current = Rc::clone(&human);
+    let mut current = Rc::clone(human);
     let mut monkeys_in_path: HashSet<Rc<MonkeyID>> = HashSet::new();
     monkeys_in_path.insert(Rc::clone(&current));
-    print!("{}", current);
+    eprint!("{}", current);
     while current != *root {
         let next = state.monkey_back_refs.get(&current).unwrap();
-        print!(" -> {}", next);
+        eprint!(" -> {}", next);
         monkeys_in_path.insert(Rc::clone(next));
         current = Rc::clone(next);
     }
-    println!("");
+    eprintln!();
 
     monkeys_in_path
 }
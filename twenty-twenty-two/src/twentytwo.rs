@@ -1,36 +1,33 @@
-use std::cmp;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
-use std::io::stdout;
 use std::str::Chars;
 
 use either::Either::{self, Left, Right};
 
+use crate::direction::Direction4;
+use crate::error::{Error, ParseResult};
+use crate::grid::{Grid, Pos, Scale, SparseGrid};
+use crate::point::Point3;
 use crate::utils;
 
-const FILE_NAME: &str = "22/input.txt";
-// const FILE_NAME: &str = "22/test_input.txt";
-
-type Scale = i64;
-
-pub fn _22a() -> Result<Scale, std::io::Error> {
-    utils::process_file(
-        FILE_NAME,
+pub fn _22a(input: utils::Input) -> Result<Scale, Error> {
+    utils::try_process_file(
+        input,
         parse_line,
         State::new_empty(),
         accumulate,
         reduce1,
-    )
+    )?.map_err(|message| Error::format(input.name(), message))
 }
 
-pub fn _22b() -> Result<Scale, std::io::Error> {
-    utils::process_file(
-        FILE_NAME,
+pub fn _22b(input: utils::Input) -> Result<Scale, Error> {
+    utils::try_process_file(
+        input,
         parse_line,
         State::new_empty(),
         accumulate,
         reduce2,
-    )
+    )?.map_err(|message| Error::format(input.name(), message))
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -54,24 +51,6 @@ impl Display for TileState {
     }
 }
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct Coord {
-    x: Scale,
-    y: Scale,
-}
-
-impl Coord {
-    pub fn new(x: Scale, y: Scale) -> Coord {
-        Coord { x, y }
-    }
-}
-
-impl Display for Coord {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {})", self.x, self.y)
-    }
-}
-
 #[derive(Debug, Clone, Copy)]
 enum Move {
     TurnLeft,
@@ -89,42 +68,46 @@ impl Display for Move {
     }
 }
 
-///Returns a map of x position (zero based) to state
-fn parse_line(line: String) -> Option<Either<HashMap<Scale, TileState>, Vec<Move>>> {
+///The x positions (zero based) of a row's tiles with their states
+type BoardLine = Vec<(Scale, TileState)>;
+
+fn parse_line(line: &str) -> ParseResult<Option<Either<BoardLine, Vec<Move>>>> {
     let mut chars = line.chars();
     let first_char = chars.next();
     match first_char {
-        Some(' ') | Some('.') | Some('#') => Some(Left(parse_board_line(first_char, chars))),
-        Some(_) => Some(Right(parse_moves(first_char, chars))),
-        _ => None,
+        Some(' ') | Some('.') | Some('#') => Ok(Some(Left(parse_board_line(first_char, chars)?))),
+        Some(_) => Ok(Some(Right(parse_moves(first_char, chars)?))),
+        _ => Ok(None),
     }
 }
 
-fn parse_board_line(first_char: Option<char>, mut chars: Chars) -> HashMap<Scale, TileState> {
+fn parse_board_line(first_char: Option<char>, mut chars: Chars) -> ParseResult<BoardLine> {
     let mut c = first_char;
     let mut index = 0;
-    let mut tile_states = HashMap::new();
+    let mut tile_states = Vec::new();
     while c.is_some() {
         match c {
-            Some(' ') => None,
-            Some('.') => tile_states.insert(index, TileState::Open),
-            Some('#') => tile_states.insert(index, TileState::Wall),
-            _ => panic!("Unrecognised tile char: {}", c.unwrap()),
+            Some(' ') => {}
+            Some('.') => tile_states.push((index, TileState::Open)),
+            Some('#') => tile_states.push((index, TileState::Wall)),
+            Some(other) => return Err(format!("Unrecognised tile char: {}", other).into()),
+            None => {}
         };
         c = chars.next();
         index += 1;
     }
-    tile_states
+    Ok(tile_states)
 }
 
-fn parse_moves(first_char: Option<char>, mut chars: Chars) -> Vec<Move> {
-    fn maybe_push_forward(num_string: &mut String, moves: &mut Vec<Move>) {
+fn parse_moves(first_char: Option<char>, mut chars: Chars) -> ParseResult<Vec<Move>> {
+    fn maybe_push_forward(num_string: &mut String, moves: &mut Vec<Move>) -> ParseResult<()> {
         if !num_string.is_empty() {
             moves.push(Move::Forward {
-                steps: num_string.parse().unwrap(),
+                steps: num_string.parse()?,
             });
             num_string.clear();
         }
+        Ok(())
     }
 
     let mut moves = Vec::new();
@@ -134,11 +117,11 @@ fn parse_moves(first_char: Option<char>, mut chars: Chars) -> Vec<Move> {
     while c.is_some() {
         match c {
             Some('R') => {
-                maybe_push_forward(&mut num_string, &mut moves);
+                maybe_push_forward(&mut num_string, &mut moves)?;
                 moves.push(Move::TurnRight)
             }
             Some('L') => {
-                maybe_push_forward(&mut num_string, &mut moves);
+                maybe_push_forward(&mut num_string, &mut moves)?;
                 moves.push(Move::TurnLeft)
             }
             Some(num) => num_string.push(num),
@@ -146,71 +129,26 @@ fn parse_moves(first_char: Option<char>, mut chars: Chars) -> Vec<Move> {
         };
         c = chars.next();
     }
-    maybe_push_forward(&mut num_string, &mut moves);
-    moves
-}
-
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
-
-impl Display for Direction {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Direction::North => "^",
-                Direction::East => ">",
-                Direction::South => "v",
-                Direction::West => "<",
-            }
-        )
-    }
-}
-
-struct Bounds {
-    min_x: Scale,
-    max_x: Scale,
-    min_y: Scale,
-    max_y: Scale,
-}
-
-impl Display for Bounds {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "({}, {}) -> ({}, {})",
-            self.min_x, self.min_y, self.max_x, self.max_y
-        )
-    }
+    maybe_push_forward(&mut num_string, &mut moves)?;
+    Ok(moves)
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 struct TileWrap {
-    coord: Coord,
-    direction: Direction,
+    coord: Pos,
+    direction: Direction4,
 }
 
 impl TileWrap {
-    pub fn new(x: i64, y: i64, direction: Direction) -> TileWrap {
-        TileWrap { coord: Coord::new(x, y), direction }
-    }
-    pub fn new_coord(coord: Coord, direction: Direction) -> TileWrap {
+    pub fn new_coord(coord: Pos, direction: Direction4) -> TileWrap {
         TileWrap { coord, direction }
     }
 }
 
 struct State {
     rows: Scale,
-    bounds: Bounds,
-    tiles: HashMap<Coord, TileState>,
+    tiles: SparseGrid<TileState>,
     moves: Vec<Move>,
-    visited_tiles: HashMap<Coord, Direction>,
     wraps: HashMap<TileWrap, TileWrap>,
 }
 
@@ -218,15 +156,8 @@ impl State {
     pub fn new_empty() -> State {
         State {
             rows: 0,
-            bounds: Bounds {
-                min_x: 0,
-                max_x: 0,
-                min_y: 0,
-                max_y: 0,
-            },
-            tiles: HashMap::new(),
+            tiles: SparseGrid::new(),
             moves: Vec::new(),
-            visited_tiles: HashMap::new(),
             wraps: HashMap::new(),
         }
     }
@@ -234,27 +165,16 @@ impl State {
 
 fn accumulate(
     mut state: State,
-    tile_states_or_moves: Option<Either<HashMap<Scale, TileState>, Vec<Move>>>,
+    tile_states_or_moves: Option<Either<BoardLine, Vec<Move>>>,
 ) -> State {
     match tile_states_or_moves {
         Some(Left(x_pos_to_state)) => {
             let y = state.rows;
-            let mut minx = state.bounds.min_x;
-            let mut maxx = state.bounds.max_x;
             for (x, tile_state) in x_pos_to_state {
-                minx = cmp::min(minx, x);
-                maxx = cmp::max(maxx, x);
-                let coord = Coord::new(x, y);
-                state.tiles.insert(coord, tile_state);
+                state.tiles.insert(Pos::new(x, y), tile_state);
             }
             State {
                 rows: state.rows + 1,
-                bounds: Bounds {
-                    min_x: cmp::min(state.bounds.min_x, minx),
-                    max_x: cmp::max(state.bounds.max_x, maxx),
-                    min_y: state.bounds.min_y,
-                    max_y: cmp::max(state.bounds.max_y, y),
-                },
                 ..state
             }
         }
@@ -263,84 +183,65 @@ fn accumulate(
     }
 }
 
-fn reduce1(mut state: State) -> Scale {
+fn reduce1(mut state: State) -> Result<Scale, String> {
     reduce(&mut state, wrapping_function1)
 }
 
-fn reduce2(mut state: State) -> Scale {
+fn reduce2(mut state: State) -> Result<Scale, String> {
     //set up the wraps
-    set_up_wraps(&mut state, FILE_NAME.contains("test"));
+    set_up_wraps(&mut state)?;
     reduce(&mut state, wrapping_function2)
 }
 
 fn reduce(
     state: &mut State,
-    wrapping_function: fn(&State, Coord, Direction) -> (Coord, Direction)
-) -> Scale {
-    output_state(&state);
+    wrapping_function: fn(&State, Pos, Direction4) -> (Pos, Direction4)
+) -> Result<Scale, String> {
+    output_state(state);
 
-    let mut coord = find_leftmost_open(&state, 0).unwrap();
-    let mut direction = Direction::East;
+    let mut coord = find_leftmost_open(state, 0).ok_or("there's no open tile in the top row of the board to start on")?;
+    let mut direction = Direction4::East;
     let my_moves = state.moves.clone();
 
     for mv in my_moves.iter() {
         match mv {
-            Move::TurnLeft => direction = calculate_new_direction(&direction, Move::TurnLeft),
-            Move::TurnRight => direction = calculate_new_direction(&direction, Move::TurnRight),
+            Move::TurnLeft => direction = direction.turn_left(),
+            Move::TurnRight => direction = direction.turn_right(),
             Move::Forward { steps } => (coord, direction) = move_forward(state, coord, direction, wrapping_function, *steps),
         }
     }
 
-    println!("Final coord: {}", coord);
-    println!("Final direction: {}", direction);
+    eprintln!("Final coord: {}", coord);
+    eprintln!("Final direction: {}", direction);
 
-    1000 * (coord.y + 1) + 4 * (coord.x + 1) + direction_value(&direction)
-}
-
-/// Calculate the new direction given the current direction and a movement
-fn calculate_new_direction(current_direction: &Direction, mv: Move) -> Direction {
-    match (current_direction, mv) {
-        (Direction::North, Move::TurnLeft) => Direction::West,
-        (Direction::East, Move::TurnLeft) => Direction::North,
-        (Direction::South, Move::TurnLeft) => Direction::East,
-        (Direction::West, Move::TurnLeft) => Direction::South,
-        (Direction::North, Move::TurnRight) => Direction::East,
-        (Direction::East, Move::TurnRight) => Direction::South,
-        (Direction::South, Move::TurnRight) => Direction::West,
-        (Direction::West, Move::TurnRight) => Direction::North,
-        _ => panic!(
-            "Unhandled new direction: {} + {} = ?",
-            current_direction, mv
-        ),
-    }
+    Ok(1000 * (coord.y + 1) + 4 * (coord.x + 1) + direction_value(&direction))
 }
 
 /// Move forwards obeying the rules!
 fn move_forward(
     state: &mut State,
-    coord: Coord,
-    direction: Direction,
-    wrapping_function: fn(&State, Coord, Direction) -> (Coord, Direction),
+    coord: Pos,
+    direction: Direction4,
+    wrapping_function: fn(&State, Pos, Direction4) -> (Pos, Direction4),
     steps: usize,
-) -> (Coord, Direction) {
+) -> (Pos, Direction4) {
     //Output
-    // println!("Move Forward: {} {} {} steps", coord, direction, steps);
+    // eprintln!("Move Forward: {} {} {} steps", coord, direction, steps);
 
-    let mut current_coord = coord.clone();
-    let mut current_direction = direction.clone();
+    let mut current_coord = coord;
+    let mut current_direction = direction;
     'outer: for _ in 0..steps {
-        let (x_inc, y_inc) = get_x_y_increments(&current_direction);
-        let mut candidate_coord = Coord::new(current_coord.x + x_inc, current_coord.y + y_inc);
-        let mut candidate_direction = current_direction.clone();
+        let mut candidate_coord = current_coord.step(current_direction);
+        let mut candidate_direction = current_direction;
 
         loop {
             //Output
-            // println!("candidate: {} {}", candidate_coord, candidate_direction);
+            // eprintln!("candidate: {} {}", candidate_coord, candidate_direction);
             (candidate_coord, candidate_direction) = wrapping_function(state, candidate_coord, candidate_direction);
-            // println!("candidate_wrapped: {} {}", candidate_coord, candidate_direction);
+            // eprintln!("candidate_wrapped: {} {}", candidate_coord, candidate_direction);
 
             let tile_state = tile_state(state, &candidate_coord);
-            // println!("tile_state: {}", tile_state);
+            // eprintln!("tile_state: {}", tile_state);
             match tile_state {
                 TileState::Open => {
                     current_coord = candidate_coord;
@@ -351,13 +252,12 @@ fn move_forward(
                     break 'outer;
                 }
                 TileState::OutOfBounds => {
-                    let (x_inc, y_inc) = get_x_y_increments(&current_direction);
-                    candidate_coord.x += x_inc;
-                    candidate_coord.y += y_inc;
-                    if candidate_coord.x < state.bounds.min_x - 1||
-                        candidate_coord.x > state.bounds.max_x + 1 ||
-                        candidate_coord.y < state.bounds.min_y - 1 ||
-                        candidate_coord.y > state.bounds.max_y + 1
+                    candidate_coord = candidate_coord.step(current_direction);
+                    let bounds = state.tiles.bounds();
+                    if candidate_coord.x < bounds.min_x - 1||
+                        candidate_coord.x > bounds.max_x + 1 ||
+                        candidate_coord.y < bounds.min_y - 1 ||
+                        candidate_coord.y > bounds.max_y + 1
                     {
                         panic!("Candidate is too out of bounds: {} {}", candidate_coord, candidate_direction);
                     }
@@ -368,77 +268,35 @@ fn move_forward(
     (current_coord, current_direction)
 }
 
-/// Given a direction of travel, give back the change in co-ordinates for a step in that direction
-fn get_x_y_increments(direction: &Direction) -> (Scale, Scale) {
-    match direction {
-        Direction::North => (0, -1),
-        Direction::East => (1, 0),
-        Direction::South => (0, 1),
-        Direction::West => (-1, 0),
-    }
-}
-
 fn output_state(state: &State) {
-    println!("{}", state.bounds);
-    for y in state.bounds.min_y..(state.bounds.max_y + 1) {
-        for x in state.bounds.min_x..(state.bounds.max_x + 1) {
-            let coord = Coord::new(x, y);
-            //display last visited first
-            match state.visited_tiles.get(&coord) {
-                Some(direction) => print!("{}", direction),
-                None => match tile_state(state, &Coord::new(x, y)) {
-                    state => print!("{}", state),
-                },
-            }
-        }
-        println!();
-    }
-    println!();
-    utils::output_into_iter_io(stdout(), "", &mut state.moves.iter());
-    println!();
+    eprintln!("Board {} with {} tiles, {} moves", state.tiles.bounds(), state.tiles.len(), state.moves.len());
 }
 
-fn find_leftmost_open(state: &State, y: Scale) -> Option<Coord> {
-    for x in state.bounds.min_x..(state.bounds.max_x + 1) {
-        let coord = Coord::new(x, y);
-        if tile_state(state, &coord) == TileState::Open {
-            return Some(Coord::new(x, y));
-        }
-    }
-    None
+fn find_leftmost_open(state: &State, y: Scale) -> Option<Pos> {
+    state.tiles.bounds().columns()
+        .map(|x| Pos::new(x, y))
+        .find(|coord| tile_state(state, coord) == TileState::Open)
 }
 
-fn tile_state(state: &State, coord: &Coord) -> TileState {
-    *state.tiles.get(coord).unwrap_or(&TileState::OutOfBounds)
+fn tile_state(state: &State, coord: &Pos) -> TileState {
+    *state.tiles.get(*coord).unwrap_or(&TileState::OutOfBounds)
 }
 
-fn direction_value(direction: &Direction) -> Scale {
+fn direction_value(direction: &Direction4) -> Scale {
     match direction {
-        Direction::North => 3,
-        Direction::East => 0,
-        Direction::South => 1,
-        Direction::West => 2,
+        Direction4::North => 3,
+        Direction4::East => 0,
+        Direction4::South => 1,
+        Direction4::West => 2,
     }
 }
 
-fn wrapping_function1(state: &State, mut coord: Coord, direction: Direction) -> (Coord, Direction) {
+fn wrapping_function1(state: &State, coord: Pos, direction: Direction4) -> (Pos, Direction4) {
     //constrain to the bounds
-    if coord.x < state.bounds.min_x {
-        coord.x = state.bounds.max_x;
-    }
-    if coord.x > state.bounds.max_x {
-        coord.x = state.bounds.min_x;
-    }
-    if coord.y < state.bounds.min_y {
-        coord.y = state.bounds.max_y;
-    }
-    if coord.y > state.bounds.max_y {
-        coord.y = state.bounds.min_y;
-    }
-    (coord, direction)
+    (state.tiles.bounds().wrap(coord), direction)
 }
 
-fn wrapping_function2(state: &State, coord: Coord, direction: Direction) -> (Coord, Direction) {
+fn wrapping_function2(state: &State, coord: Pos, direction: Direction4) -> (Pos, Direction4) {
     //wrap if it's set up to do it
     match state.wraps.get(&TileWrap::new_coord(coord, direction)) {
         Some(wrap) => (wrap.coord, wrap.direction),
@@ -446,276 +304,133 @@ fn wrapping_function2(state: &State, coord: Coord, direction: Direction) -> (Coo
     }
 }
 
-fn set_up_wraps(state: &mut State, test_input: bool) {
-    //test_input
-    if test_input {
-        set_up_test_wraps(state);
-    } else {
-        set_up_main_wraps(state);
+fn set_up_wraps(state: &mut State) -> Result<(), String> {
+    //the board is made of 6 square faces so we can deduce the side length from the tiles
+    let side_length = ((state.tiles.len() / 6) as f64).sqrt() as Scale;
+    if side_length == 0 || state.tiles.len() as Scale != 6 * side_length * side_length {
+        return Err(format!("the board has {} tiles, which isn't 6 square faces", state.tiles.len()));
+    }
+    let net: HashSet<Pos> = state.tiles.positions()
+        .map(|coord| Pos::new(coord.x / side_length, coord.y / side_length))
+        .collect();
+    if net.len() != 6 {
+        return Err(format!("the board isn't 6 faces of side length {} laid out on a grid", side_length));
+    }
+    let faces = fold(&net)?;
+    for face in faces.iter() {
+        for direction in DIRECTIONS {
+            if net.contains(&face.net.step(direction)) {
+                //Already next to each other, stepping over the edge is right as it is
+                continue;
+            }
+            //The face on the cube over the edge, and its edge that meets this one
+            let outward = face.outward(direction);
+            let other = faces.iter().find(|other| other.normal == outward).ok_or("the board doesn't fold into a cube")?;
+            let other_direction = DIRECTIONS
+                .into_iter()
+                .find(|&d| other.outward(d) == face.normal)
+                .ok_or("the board doesn't fold into a cube")?;
+            //The edges meet corner to corner, but may run in opposite directions across the board
+            let reversed = face.corners(direction)[0] != other.corners(other_direction)[0];
+            for i in 0..side_length {
+                let other_i = if reversed { side_length - 1 - i } else { i };
+                state.wraps.insert(
+                    TileWrap::new_coord(face.edge_tile(direction, i, side_length).step(direction), direction),
+                    TileWrap::new_coord(other.edge_tile(other_direction, other_i, side_length), other_direction.reverse())
+                );
+            }
+        }
     }
+    Ok(())
 }
 
-/// grid is in the following format
-///  12
-///  3
-/// 45
-/// 6
-///
-/// FIXME:  We should only need to define the mapping once and derive the reverse, i.e.
-/// if 1N -> 3E then we know 3W -> 1S
-fn set_up_main_wraps(state: &mut State) {
-    let side_length = 50;
-    // 3 west to 4 south
-    setup_tile_wraps(state, side_length,
-        1, 1, Direction::West,
-        0, 2, Direction::South, false
-    );
-    // 4 north to 3 east
-    setup_tile_wraps(state, side_length,
-        0, 2, Direction::North,
-        1, 1, Direction::East, false
-    );
-    // 3 east to 2 north
-    setup_tile_wraps(state, side_length,
-        1, 1, Direction::East,
-        2, 0, Direction::North, false
-    );
-    // 2 south to 3 west
-    setup_tile_wraps(state, side_length,
-        2, 0, Direction::South,
-        1, 1, Direction::West, false
-    );
-    // 5 south to 6 west
-    setup_tile_wraps(state, side_length,
-        1, 2, Direction::South,
-        0, 3, Direction::West, false
-    );
-    // 6 east to 5 north
-    setup_tile_wraps(state, side_length,
-        0, 3, Direction::East,
-        1, 2, Direction::North, false
-    );
-    // 2 east to 5 west
-    setup_tile_wraps(state, side_length,
-        2, 0, Direction::East,
-        1, 2, Direction::West, true
-    );
-    // 5 east to 2 west
-    setup_tile_wraps(state, side_length,
-        1, 2, Direction::East,
-        2, 0, Direction::West, true
-    );
-    // 1 west to 4 east
-    setup_tile_wraps(state, side_length,
-        1, 0, Direction::West,
-        0, 2, Direction::East, true
-    );
-    // 4 west to 1 east
-    setup_tile_wraps(state, side_length,
-        0, 2, Direction::West,
-        1, 0, Direction::East, true
-    );
-    // 1 north to 6 east
-    setup_tile_wraps(state, side_length,
-        1, 0, Direction::North,
-        0, 3, Direction::East, false
-    );
-    // 6 west to 1 south
-    setup_tile_wraps(state, side_length,
-        0, 3, Direction::West,
-        1, 0, Direction::South, false
-    );
-    // 2 north to 6 north
-    setup_tile_wraps(state, side_length,
-        2, 0, Direction::North,
-        0, 3, Direction::North, false
-    );
-    // 6 south to 2 south
-    setup_tile_wraps(state, side_length,
-        0, 3, Direction::South,
-        2, 0, Direction::South, false
-    );
-}
+const DIRECTIONS: [Direction4; 4] = [Direction4::North, Direction4::East, Direction4::South, Direction4::West];
 
-/// FIXME:  Convert all of these to use setup_tile_wraps.
-fn set_up_test_wraps(state: &mut State) {
-    let side_length = 4;
-    //North
-    //1 north to 2 south
-    setup_tile_wraps(state, side_length,
-        3, 0, Direction::North,
-        0, 1, Direction::South,
-        true
-    );
-    //2 north to 1 south
-    setup_tile_wraps(state, side_length,
-        0, 1, Direction::North,
-        2, 0, Direction::South,
-        true
-    );
-    //3 north to 1 east
-    setup_tile_wraps(state, side_length,
-        1, 1, Direction::North,
-        2, 0, Direction::East,
-        false
-    );
-    //6 north to 4 west
-    setup_tile_wraps(state, side_length,
-        3, 2, Direction::North,
-        2, 1, Direction::West,
-        false
-    );
-    //South
-    //2 south to 5 north
-    for x in 0..side_length {
-        state.wraps.insert(
-            TileWrap::new(x, side_length * 2, Direction::South),
-            TileWrap::new(side_length * 3 - 1 - x, side_length * 3 - 1, Direction::North)
-        );
-    }
-    //3 south to 5 east
-    for x in 0..side_length {
-        state.wraps.insert(
-            TileWrap::new(side_length + x, side_length * 2, Direction::South),
-            TileWrap::new(side_length * 2, side_length * 3 - 1 - x, Direction::East)
-        );
-    }
-    //5 south to 2 north
-    for x in 0..side_length {
-        state.wraps.insert(
-            TileWrap::new(side_length * 2 + x, side_length * 3, Direction::South),
-            TileWrap::new(side_length - 1 - x, side_length * 2 - 1, Direction::North)
-        );
-    }
-    //6 south to 2 East
-    for x in 0..side_length {
-        state.wraps.insert(
-            TileWrap::new(side_length * 3 + x, side_length * 3, Direction::South),
-            TileWrap::new(0, side_length * 2 - 1 - x, Direction::East)
-        );
-    }
-    //east
-    //1 east to 6 west
-    for y in 0..side_length {
-        state.wraps.insert(
-            TileWrap::new(side_length * 3, y, Direction::East),
-            TileWrap::new(side_length * 4 - 1, side_length * 3 - 1 - y, Direction::West)
-        );
-    }
-    //4 east to 6 south
-    for y in 0..side_length {
-        state.wraps.insert(
-            TileWrap::new(side_length * 3, side_length + y, Direction::East),
-            TileWrap::new(side_length * 4 - 1 - y, side_length * 2, Direction::South)
-        );
-    }
-    //6 east to 1 west
-    for y in 0..side_length {
-        state.wraps.insert(
-            TileWrap::new(side_length * 4, side_length * 2 + y, Direction::East),
-            TileWrap::new(side_length * 3 - 1, side_length - 1 - y, Direction::South)
-        );
+/// A face of the net folded into a cube centred on the origin, with which way it faces, and the
+/// ways its x (right) and y (down) on the board go round the cube
+#[derive(Debug, Clone, Copy)]
+struct Face {
+    ///Where the face is on the board, in units of the side length
+    net: Pos,
+    normal: Point3<Scale>,
+    right: Point3<Scale>,
+    down: Point3<Scale>,
+}
+
+impl Face {
+    ///The face over the edge in direction, folded down round that edge
+    fn roll(&self, direction: Direction4) -> Face {
+        let Face { net, normal, right, down } = *self;
+        let net = net.step(direction);
+        match direction {
+            Direction4::North => Face { net, normal: -down, right, down: normal },
+            Direction4::East => Face { net, normal: right, right: -normal, down },
+            Direction4::South => Face { net, normal: down, right, down: -normal },
+            Direction4::West => Face { net, normal: -right, right: normal, down },
+        }
     }
-    //west
-    //1 west to 3 south
-    for y in 0..side_length {
-        state.wraps.insert(
-            TileWrap::new(side_length * 2 - 1, y, Direction::West),
-            TileWrap::new(side_length * 2 - 1 - y, side_length, Direction::South)
-        );
+
+    ///Which way is out of the face across its edge in direction
+    fn outward(&self, direction: Direction4) -> Point3<Scale> {
+        match direction {
+            Direction4::North => -self.down,
+            Direction4::East => self.right,
+            Direction4::South => self.down,
+            Direction4::West => -self.right,
+        }
     }
-    //2 west to 6 north
-    for y in 0..side_length {
-        state.wraps.insert(
-            TileWrap::new(- 1, side_length + y, Direction::West),
-            TileWrap::new(side_length * 4 - 1 - y, side_length * 3 - 1, Direction::North)
-        );
+
+    ///The corners of the cube at the ends of the edge in direction, going the way x or y goes along it
+    fn corners(&self, direction: Direction4) -> [Point3<Scale>; 2] {
+        let corner = |right: Scale, down: Scale| self.normal + self.right * right + self.down * down;
+        match direction {
+            Direction4::North => [corner(-1, -1), corner(1, -1)],
+            Direction4::East => [corner(1, -1), corner(1, 1)],
+            Direction4::South => [corner(-1, 1), corner(1, 1)],
+            Direction4::West => [corner(-1, -1), corner(-1, 1)],
+        }
     }
-    //5 west to 3 north
-    for y in 0..side_length {
-        state.wraps.insert(
-            TileWrap::new(side_length * 2 - 1, side_length * 2 + y, Direction::West),
-            TileWrap::new(side_length * 2 - 1 - y, side_length * 2 - 1, Direction::North)
-        );
+
+    ///The board position of the ith tile along the edge in direction
+    fn edge_tile(&self, direction: Direction4, i: Scale, side_length: Scale) -> Pos {
+        let top_left = self.net * side_length;
+        let far = side_length - 1;
+        top_left + match direction {
+            Direction4::North => Pos::new(i, 0),
+            Direction4::East => Pos::new(far, i),
+            Direction4::South => Pos::new(i, far),
+            Direction4::West => Pos::new(0, i),
+        }
     }
 }
 
-//FIXME:  Do the converse directions automatically to avoid setting up the opposites
-fn setup_tile_wraps(state: &mut State, side_length: Scale,
-    from_grid_x: Scale, from_grid_y: Scale, from_direction: Direction,
-    to_grid_x: Scale, to_grid_y: Scale, to_direction: Direction,
-    reverse: bool)
-{
-    let (from_x_min, from_x_max_plus_1, from_y_min, from_y_max_plus_1) = match from_direction {
-        Direction::North => (
-            side_length * from_grid_x,
-            side_length * (from_grid_x + 1),
-            side_length * from_grid_y - 1,
-            side_length * from_grid_y
-        ),
-        Direction::East => (
-            side_length * (from_grid_x + 1),
-            side_length * (from_grid_x + 1) + 1,
-            side_length * from_grid_y,
-            side_length * (from_grid_y + 1)
-        ),
-        Direction::South => (
-            side_length * from_grid_x,
-            side_length * (from_grid_x + 1),
-            side_length * (from_grid_y + 1),
-            side_length * (from_grid_y + 1) + 1
-        ),
-        Direction::West => (
-            side_length * from_grid_x - 1,
-            side_length * from_grid_x,
-            side_length * from_grid_y,
-            side_length * (from_grid_y + 1)
-        ),
-    };
-    let (to_start_x, inc_x, to_start_y, inc_y) = match (to_direction, reverse) {
-        (Direction::North, false) => (
-            side_length * to_grid_x, 1,
-            side_length * (to_grid_y + 1) - 1, 0
-        ),
-        (Direction::North, true) => (
-            side_length * (to_grid_x + 1) - 1, -1,
-            side_length * (to_grid_y + 1) - 1, 0
-        ),
-        (Direction::East, false) => (
-            side_length * to_grid_x, 0,
-            side_length * to_grid_y, 1
-        ),
-        (Direction::East, true) => (
-            side_length * to_grid_x, 0,
-            side_length * (to_grid_y + 1) - 1, -1
-        ),
-        (Direction::South, false) => (
-            side_length * to_grid_x, 1,
-            side_length * to_grid_y, 0
-        ),
-        (Direction::South, true) => (
-            side_length * (to_grid_x + 1) - 1, -1,
-            side_length * to_grid_y, 0
-        ),
-        (Direction::West, false) => (
-            side_length * (to_grid_x + 1) - 1, 0,
-            side_length * to_grid_y, 1
-        ),
-        (Direction::West, true) => (
-            side_length * (to_grid_x + 1) - 1, 0,
-            side_length * (to_grid_y + 1) - 1, -1
-        ),
-    };
-    let mut dest_x = to_start_x;
-    let mut dest_y = to_start_y;
-    for source_x in from_x_min..from_x_max_plus_1 {
-        for source_y in from_y_min..from_y_max_plus_1 {
-            state.wraps.insert(
-                TileWrap::new(source_x, source_y, from_direction),
-                TileWrap::new(dest_x, dest_y, to_direction)
-            );
-            dest_x += inc_x;
-            dest_y += inc_y;
+/// Fold the faces of the net into a cube, rolling out from the top left face to the rest. It is
+/// an error for them not to be joined up, or for two of them to end up on the same side.
+fn fold(net: &HashSet<Pos>) -> Result<Vec<Face>, String> {
+    let first = *net.iter().min_by_key(|face| (face.y, face.x)).ok_or("there are no faces to fold")?;
+    let mut faces = vec![Face {
+        net: first,
+        normal: Point3::new(0, 0, -1),
+        right: Point3::new(1, 0, 0),
+        down: Point3::new(0, 1, 0),
+    }];
+    let mut next = 0;
+    while next < faces.len() {
+        let face = faces[next];
+        for direction in DIRECTIONS {
+            let neighbour = face.net.step(direction);
+            if net.contains(&neighbour) && faces.iter().all(|folded| folded.net != neighbour) {
+                faces.push(face.roll(direction));
+            }
         }
+        next += 1;
+    }
+    if faces.len() != net.len() {
+        return Err(String::from("the faces of the board aren't all joined up"));
     }
-}
\ No newline at end of file
+    let sides: HashSet<Point3<Scale>> = faces.iter().map(|face| face.normal).collect();
+    if sides.len() != faces.len() {
+        return Err(String::from("the board doesn't fold into a cube, faces overlap"));
+    }
+    Ok(faces)
+}
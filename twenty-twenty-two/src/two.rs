@@ -1,18 +1,17 @@
-use std::io::Error;
+use crate::error::Error;
 use crate::utils;
 
-pub fn _2a() -> Result<i64, Error>{
-    run_calc(parse_line_1)
+pub fn _2a(input: utils::Input) -> Result<i64, Error>{
+    run_calc(input, parse_line_1)
 }
 
-pub fn _2b() -> Result<i64, Error>{
-    run_calc(parse_line_2)
+pub fn _2b(input: utils::Input) -> Result<i64, Error>{
+    run_calc(input, parse_line_2)
 }
 
-fn run_calc(parse_line_fun: fn (String) -> Option<(i64, i64)>) -> Result<i64, Error>{
-    let filename = "2/input.txt";
+fn run_calc(input: utils::Input, parse_line_fun: fn (String) -> Option<(i64, i64)>) -> Result<i64, Error>{
     utils::process_file(
-        filename,
+        input,
         parse_line_fun,
         (0, 0),
         accumulate,
@@ -52,13 +51,13 @@ fn play1(p1: char, p2: char) -> (i64, i64) {
     let play_2 = get_play(p2);
     match (play_1, play_2) {
         (Play::Rock, Play::Rock) => (1 + 3, 1 + 3),
-        (Play::Rock, Play::Paper) => (1 + 0, 2 + 6),
-        (Play::Rock, Play::Scissors) => (1 + 6, 3 + 0),
-        (Play::Paper, Play::Rock) => (2 + 6, 1 + 0),
+        (Play::Rock, Play::Paper) => (1, 2 + 6),
+        (Play::Rock, Play::Scissors) => (1 + 6, 3),
+        (Play::Paper, Play::Rock) => (2 + 6, 1),
         (Play::Paper, Play::Paper) => (2 + 3, 2 + 3),
-        (Play::Paper, Play::Scissors) => (2 + 0, 3 + 6),
-        (Play::Scissors, Play::Rock) => (3 + 0, 1 + 6),
-        (Play::Scissors, Play::Paper) => (3 + 6, 2 + 0),
+        (Play::Paper, Play::Scissors) => (2, 3 + 6),
+        (Play::Scissors, Play::Rock) => (3, 1 + 6),
+        (Play::Scissors, Play::Paper) => (3 + 6, 2),
         (Play::Scissors, Play::Scissors) => (3 + 3, 3 + 3),
     }
 }
@@ -71,15 +70,15 @@ fn play2(p1: char, p2: char) -> (i64, i64) {
     let play = get_play(p1);
     let aim = get_aim(p2);
     match (play, aim) {
-        (Play::Rock, Aim::Lose) => (1 + 6, 3 + 0),
+        (Play::Rock, Aim::Lose) => (1 + 6, 3),
         (Play::Rock, Aim::Draw) => (1 + 3, 1 + 3),
-        (Play::Rock, Aim::Win) => (1 + 0, 2 + 6),
-        (Play::Paper, Aim::Lose) => (2 + 6, 1 + 0),
+        (Play::Rock, Aim::Win) => (1, 2 + 6),
+        (Play::Paper, Aim::Lose) => (2 + 6, 1),
         (Play::Paper, Aim::Draw) => (2 + 3, 2 + 3),
-        (Play::Paper, Aim::Win) => (2 + 0, 3 + 6),
-        (Play::Scissors, Aim::Lose) => (3 + 6, 2 + 0),
+        (Play::Paper, Aim::Win) => (2, 3 + 6),
+        (Play::Scissors, Aim::Lose) => (3 + 6, 2),
         (Play::Scissors, Aim::Draw) => (3 + 3, 3 + 3),
-        (Play::Scissors, Aim::Win) => (3 + 0, 1 + 6),
+        (Play::Scissors, Aim::Win) => (3, 1 + 6),
     }
 }
 
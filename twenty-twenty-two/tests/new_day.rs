@@ -0,0 +1,94 @@
+//new-day on a copy of this crate's files with day 25 taken out, it should be put back as it was
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_common::scaffold::{self, Layout};
+use twenty_twenty_two::runner::YEAR;
+
+const LAYOUT: &Layout = &YEAR.layout;
+const FILES: [&str; 5] = [LAYOUT.lib, LAYOUT.runner, LAYOUT.regression, LAYOUT.template, LAYOUT.expected_answers];
+
+///A copy of the files needed by new-day in a fresh directory, with the edit applied to each
+fn copy_crate(name: &str, edit: impl Fn(&str, String) -> String) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("src")).unwrap();
+    for file in FILES {
+        fs::write(root.join(file), edit(file, fs::read_to_string(file).unwrap())).unwrap();
+    }
+    root
+}
+
+///How many entries SOLUTIONS has for the day and part, whether solution! lines or written out
+fn entries(runner: &str, day: u8, part: &str) -> usize {
+    let written_out = format!("        day: {},\n        part: Part::{},\n", day, part);
+    runner.matches(&format!("solution!({}, {},", day, part)).count() + runner.matches(&written_out).count()
+}
+
+fn without_day_25(file: &str, contents: String) -> String {
+    match file {
+        "src/lib.rs" => contents.replace("pub mod twentyfive;\n", ""),
+        "src/runner.rs" => contents
+            .replace(" twentyfive,", "")
+            .lines()
+            .filter(|line| !line.contains("twentyfive::"))
+            .map(|line| format!("{}\n", line))
+            .collect(),
+        "src/regression.rs" => contents.replace("    day_25: 25,\n", ""),
+        _ => contents,
+    }
+}
+
+fn read(root: &Path, file: &str) -> String {
+    fs::read_to_string(root.join(file)).unwrap()
+}
+
+#[test]
+fn new_day_registers_the_day() {
+    let root = copy_crate("new-day", without_day_25);
+    scaffold::new_day(&root, LAYOUT, 25).unwrap();
+
+    let module = read(&root, "src/twentyfive.rs");
+    assert!(module.contains("pub fn _25a(") && module.contains("pub fn _25b("), "{}", module);
+    assert!(read(&root, "src/lib.rs").contains("pub mod twentyfive;\n"));
+    //Only 25a is put back, in order, the imports are rewrapped as rustfmt has them and 25b's
+    // written out entry is left alone
+    let runner = read(&root, "src/runner.rs");
+    assert_eq!((entries(&runner, 25, "A"), entries(&runner, 25, "B")), (1, 1), "{}", runner);
+    assert_eq!(runner, read(Path::new("."), "src/runner.rs"));
+    assert_eq!(read(&root, "src/regression.rs"), read(Path::new("."), "src/regression.rs"));
+    //25a's test answer is already listed, and there is no 25b to have one
+    assert_eq!(read(&root, "expected_answers.txt"), read(Path::new("."), "expected_answers.txt"));
+    assert_eq!(read(&root, "25/input.txt"), "");
+    assert_eq!(read(&root, "25/test_input.txt"), "");
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn new_day_registers_both_parts_of_an_unlisted_day() {
+    //The written out 25b made into a day after it, so there is nothing for day 25
+    let root = copy_crate("unlisted-day", |file, contents| {
+        without_day_25(file, contents).replace("        day: 25,\n", "        day: 26,\n")
+    });
+    scaffold::new_day(&root, LAYOUT, 25).unwrap();
+
+    let runner = read(&root, "src/runner.rs");
+    assert_eq!((entries(&runner, 25, "A"), entries(&runner, 25, "B")), (1, 1), "{}", runner);
+    assert!(runner.contains("    solution!(25, A, twentyfive::_25a),\n    solution!(25, B, twentyfive::_25b),\n    Solution {\n"));
+    assert!(read(&root, "expected_answers.txt").ends_with("\n25 b test_input.txt TODO\n"));
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn new_day_refuses_an_existing_day() {
+    let root = copy_crate("existing-day", |_, contents| contents);
+    fs::write(root.join("src/seven.rs"), "").unwrap();
+    let error = scaffold::new_day(&root, LAYOUT, 7).unwrap_err();
+    assert!(error.to_string().ends_with("day 7 already exists"), "{}", error);
+    assert!(scaffold::new_day(&root, LAYOUT, 26).is_err());
+
+    fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,202 @@
+//Uses the library the way another crate would, through its public API only
+
+use std::collections::HashSet;
+
+use twenty_twenty_two::nineteen::{self, Robot};
+use twenty_twenty_two::seventeen::{self, Chamber};
+use twenty_twenty_two::sixteen::{self, Action};
+use twenty_twenty_two::thirteen::Item;
+use twenty_twenty_two::utils::Input;
+use twenty_twenty_two::{eight, five, one, seven, twelve, twentyfive, twentyfour, twentytwo};
+
+const DAY_1_EXAMPLE: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+
+#[test]
+fn solvers_take_input_text() {
+    assert_eq!(one::_1a(Input::Text(DAY_1_EXAMPLE)).unwrap(), 24000);
+    assert_eq!(one::_1b(Input::Text(DAY_1_EXAMPLE)).unwrap(), 45000);
+}
+
+#[test]
+fn errors_in_input_text_give_the_line() {
+    let error = one::_1a(Input::Text("1000\nabc\n")).unwrap_err();
+    assert_eq!(error.to_string(), "input:2: invalid digit found in string: 'abc'");
+}
+
+#[test]
+fn malformed_lines_are_errors_rather_than_panics() {
+    let error = |result: Result<_, twenty_twenty_two::error::Error>| result.map(|_: u64| ()).unwrap_err().to_string();
+    assert_eq!(error(eight::_8a(Input::Text("30373\n2x512\n"))), "input:2: 'x' isn't a tree height: '2x512'");
+    assert_eq!(error(eight::_8a(Input::Text("303\n2551\n"))), "input:2: the row is 4 long where the first is 3: '2551'");
+    assert_eq!(error(seven::_7a(Input::Text("$ cd /\n$ ls\nfile.txt\n"))),
+        "input:3: expected a cd or ls command, or a dir or file that ls listed: 'file.txt'");
+
+    let crates = "    [D]\n[N] [C]\n 1   2\n\nmove 1 from 2 to 1\nmove 3 from 1 to 2\n";
+    assert_eq!(five::_5a(Input::Text(crates)).unwrap_err().to_string(), "input:6: stack 1 only has 2 crates: 'move 3 from 1 to 2'");
+    assert!(twentyfour::_24a(Input::Text("#.###\n#.?.#\n###.#\n")).is_err());
+    assert!(twentytwo::_22a(Input::Text("..x.\n\n10R5\n")).is_err());
+    assert!(twentytwo::_22a(Input::Text("....\n\n99999999999999999999999R5\n")).is_err());
+
+    assert_eq!(twelve::_12a(Input::Text("Sab\nabcE\n")).unwrap_err().to_string(), "input:2: the row is 4 long where the first is 3: 'abcE'");
+    assert_eq!(twelve::_12a(Input::Text("abc\n")).unwrap_err().to_string(), "input: there's no start marked with S");
+    assert_eq!(twelve::_12b(Input::Text("Sab\n")).unwrap_err().to_string(), "input: there's no end marked with E");
+}
+
+#[test]
+fn packets_sort_in_distress_signal_order() {
+    let packet = |s: &str| s.parse::<Item>().unwrap();
+    assert!(packet("[1,1,3,1,1]") < packet("[1,1,5,1,1]"));
+    assert!(packet("[[1],[2,3,4]]") < packet("[[1],4]"));
+    assert!(packet("[9]") > packet("[[8,7,6]]"));
+    assert!(packet("[7,7,7]") > packet("[7,7]"));
+    assert_eq!(packet("[[2]]"), packet("[2]"));
+    assert!("[1,2".parse::<Item>().is_err());
+}
+
+#[test]
+fn snafu_conversions() {
+    assert_eq!(twentyfive::to_snafu(1747), "1=-0-2");
+    assert_eq!(twentyfive::from_snafu("2=-01").unwrap(), 976);
+    for value in 0..2000 {
+        assert_eq!(twentyfive::from_snafu(&twentyfive::to_snafu(value)).unwrap(), value);
+    }
+}
+
+#[test]
+fn valve_schedules_for_any_number_of_agents() {
+    let valves = sixteen::read_valves(Input::File("16/test_input.txt")).unwrap();
+    let opened = |actions: &[Action]| -> Vec<String> {
+        actions
+            .iter()
+            .filter_map(|action| match action {
+                Action::Open(id) => Some(id.to_string()),
+                Action::MoveTo(_) => None,
+            })
+            .collect()
+    };
+
+    let alone = sixteen::best_schedule(&valves, 1, 30).unwrap();
+    assert_eq!(alone.total_pressure, 1651);
+    assert_eq!(opened(&alone.actions[0]), ["DD", "BB", "JJ", "HH", "EE", "CC"]);
+    assert_eq!(alone.actions[0].len(), 24);
+
+    assert_eq!(sixteen::best_schedule(&valves, 2, 26).unwrap().total_pressure, 1707);
+
+    //More agents can't do worse, and never open the same valve twice
+    let crowd = sixteen::best_schedule(&valves, 3, 26).unwrap();
+    assert_eq!(crowd.actions.len(), 3);
+    assert!(crowd.total_pressure >= 1707);
+    let all_opened: Vec<String> = crowd.actions.iter().flat_map(|actions| opened(actions)).collect();
+    assert_eq!(all_opened.iter().collect::<HashSet<_>>().len(), all_opened.len());
+    assert!(crowd.actions.iter().all(|actions| actions.len() < 26));
+
+    assert_eq!(sixteen::best_schedule(&valves, 2, 1).unwrap().total_pressure, 0);
+
+    let error = sixteen::_16a(Input::Text("Valve BB has flow rate=13; tunnel leads to valve BB\n")).unwrap_err();
+    assert_eq!(error.to_string(), "input: there's no valve AA to start at");
+    let error = sixteen::_16a(Input::Text("Valve AA has flow rate=0; tunnel leads to valve BB\n")).unwrap_err();
+    assert_eq!(error.to_string(), "input: a tunnel leads from AA to BB, which isn't listed");
+}
+
+#[test]
+fn geode_builds_for_each_blueprint() {
+    let blueprints = nineteen::read_blueprints(Input::File("19/test_input.txt")).unwrap();
+    let geodes = |max_steps| -> Vec<usize> {
+        nineteen::best_builds_in_parallel(&blueprints, max_steps).iter().map(|builds| builds.geodes).collect()
+    };
+    assert_eq!(geodes(24), [9, 12]);
+    assert_eq!(geodes(32), [56, 62]);
+
+    let builds = nineteen::best_builds(&blueprints[0], 24);
+    assert_eq!(builds.blueprint_id, 1);
+    assert!(builds.order.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    assert_eq!(builds.order.last().unwrap().1, Robot::GeodeCracking);
+    assert_eq!(nineteen::best_builds(&blueprints[0], 2).order, []);
+}
+
+#[test]
+fn rocks_fall_in_any_chamber() {
+    let winds = Input::File("17/test_input.txt");
+    assert_eq!(seventeen::tower_height(winds, &Chamber::standard(), 2022).unwrap(), 3068);
+
+    //Squares that fill a chamber as wide as them just stack up
+    let squares = Chamber::read(Input::Text("width: 2\ndrop left: 0\n\n##\n##\n")).unwrap();
+    assert_eq!(seventeen::tower_height(winds, &squares, 10).unwrap(), 20);
+    assert_eq!(seventeen::repeating_tower_height(winds, &squares, 1_000_000_000_000).unwrap(), 2_000_000_000_000);
+
+    //Settings can be left out, and rocks can have space drawn around them
+    let bars = Chamber::read(Input::Text("....\n.##.\n")).unwrap();
+    //All pushed against the left wall, one on top of the other
+    assert_eq!(seventeen::tower_height(Input::Text("<<<<\n"), &bars, 7).unwrap(), 7);
+
+    let error = Chamber::read(Input::Text("width: 3\n\n##\n#o\n")).err().unwrap();
+    assert_eq!(error.to_string(), "input:3: unexpected 'o' in a rock, draw it with '#' and '.': '##'");
+    assert!(Chamber::read(Input::Text("width: 9\n\n#\n")).is_err());
+    assert!(Chamber::read(Input::Text("width: 3\n\n####\n")).is_err());
+    assert!(Chamber::read(Input::Text(&"#\n".repeat(129))).is_err());
+
+    //Two bars stack up against the left wall, then a block is blown right and falls past the
+    // rows that are kept
+    let well = format!("width: 2\ndrop left: 0\ndrop height: 0\n\n{0}\n{0}\n#\n", "#\n".repeat(100));
+    let well = Chamber::read(Input::Text(&well)).unwrap();
+    let error = seventeen::tower_height(Input::Text("<<>\n"), &well, 3).unwrap_err();
+    assert!(error.to_string().ends_with("below the rows that are kept"), "{}", error);
+    assert!(seventeen::repeating_tower_height(Input::Text("<<>\n"), &well, 3).is_err());
+
+    let error = seventeen::tower_height(Input::Text(""), &Chamber::standard(), 1).unwrap_err();
+    assert_eq!(error.to_string(), "input: there are no jets of gas, expected a line of '<' and '>'");
+    assert!(seventeen::repeating_tower_height(Input::Text("\n"), &Chamber::standard(), 1).is_err());
+}
+
+///An open board of the faces of a net, each 2 tiles square, followed by the moves
+fn open_net(faces: &[(usize, usize)], moves: &str) -> String {
+    let rows = 2 * (faces.iter().map(|face| face.1).max().unwrap() + 1);
+    let mut board = String::new();
+    for y in 0..rows {
+        let width = 2 * (faces.iter().filter(|face| face.1 == y / 2).map(|face| face.0).max().unwrap() + 1);
+        let row: String = (0..width).map(|x| if faces.contains(&(x / 2, y / 2)) { '.' } else { ' ' }).collect();
+        board.push_str(&row);
+        board.push('\n');
+    }
+    format!("{}\n{}\n", board, moves)
+}
+
+#[test]
+fn any_cube_net_folds() {
+    //Every one of the 11 nets of a cube, in one of their orientations
+    let nets: [&[(usize, usize)]; 11] = [
+        //A row of four with a face above and below it
+        &[(0, 0), (0, 1), (1, 1), (2, 1), (3, 1), (0, 2)],
+        &[(0, 0), (0, 1), (1, 1), (2, 1), (3, 1), (1, 2)],
+        &[(0, 0), (0, 1), (1, 1), (2, 1), (3, 1), (2, 2)],
+        &[(0, 0), (0, 1), (1, 1), (2, 1), (3, 1), (3, 2)],
+        &[(1, 0), (0, 1), (1, 1), (2, 1), (3, 1), (1, 2)],
+        &[(1, 0), (0, 1), (1, 1), (2, 1), (3, 1), (2, 2)],
+        //Two, three and one
+        &[(0, 0), (1, 0), (1, 1), (2, 1), (3, 1), (1, 2)],
+        &[(0, 0), (1, 0), (1, 1), (2, 1), (3, 1), (2, 2)],
+        &[(0, 0), (1, 0), (1, 1), (2, 1), (3, 1), (3, 2)],
+        //Stairs and a zigzag
+        &[(0, 0), (1, 0), (1, 1), (2, 1), (2, 2), (3, 2)],
+        &[(0, 0), (1, 0), (2, 0), (2, 1), (3, 1), (4, 1)],
+    ];
+    for net in nets {
+        let start_x = 2 * net.iter().filter(|face| face.1 == 0).map(|face| face.0).min().unwrap() as i64;
+        //Going straight round a cube with no walls comes back to where it started, facing the same way
+        for (moves, facing) in [("8", 0), ("R8", 1), ("LL8", 2), ("L8", 3), ("1R8L8", 0)] {
+            let board = open_net(net, moves);
+            let expected = 1000 + 4 * (start_x + 1 + moves.starts_with('1') as i64) + facing;
+            assert_eq!(twentytwo::_22b(Input::Text(&board)).unwrap(), expected, "{:?} {}", net, moves);
+        }
+    }
+
+    let error = |board: &str| twentytwo::_22b(Input::Text(board)).unwrap_err().to_string();
+    //Six faces that overlap when folded, six that aren't joined up, and five
+    assert_eq!(error(&open_net(&[(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)], "8")),
+        "input: the board doesn't fold into a cube, faces overlap");
+    assert_eq!(error(&open_net(&[(0, 0), (1, 0), (2, 0), (4, 0), (4, 1), (4, 2)], "8")),
+        "input: the faces of the board aren't all joined up");
+    assert!(error(&open_net(&[(0, 0), (1, 0), (2, 0), (2, 1), (3, 1)], "8")).contains("isn't 6 square faces"));
+    assert_eq!(error(""), "input: the board has 0 tiles, which isn't 6 square faces");
+    assert!(twentytwo::_22a(Input::Text("")).is_err());
+}